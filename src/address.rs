@@ -3,14 +3,55 @@
 
 use embedded_can::Id;
 
+use super::error::ZanCanError;
+use super::ZanCanFrame;
+
 
 pub const ADDRESS_BIT_LENGTH: usize = 8;
+//Highest address that fits in ADDRESS_BIT_LENGTH bits
+pub const MAX_ADDRESS: u8 = ((1u16 << ADDRESS_BIT_LENGTH) - 1) as u8;
+//Extended ids keep the same 3 bit frame type at the top and give the other 26 bits to the address
+pub const EXTENDED_ADDRESS_BIT_LENGTH: usize = 26;
+pub const MAX_EXTENDED_ADDRESS: u32 = (1u32 << EXTENDED_ADDRESS_BIT_LENGTH) - 1;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+//Ordered by numeric value so routing tables can do range checks, BROADCAST sorts first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ZanCanAddress {
     id: u8
 }
 
+impl ZanCanAddress {
+    //Address 0x00 is broadcast, meaning all nodes. It is the lowest address value on the bus
+    pub const BROADCAST: ZanCanAddress = ZanCanAddress { id: 0x00 };
+
+    pub fn is_broadcast(&self) -> bool {
+        *self == ZanCanAddress::BROADCAST
+    }
+
+    pub const fn value(&self) -> u8 {
+        self.id
+    }
+
+    //Checked constructor, None if the value doesn't fit in the address bits of an id.
+    //Can't fail while addresses are a full byte but guards against ADDRESS_BIT_LENGTH shrinking
+    #[allow(clippy::absurd_extreme_comparisons)]
+    pub fn new(value: u8) -> Option<ZanCanAddress> {
+        if value > MAX_ADDRESS {
+            None
+        } else {
+            Some(ZanCanAddress { id: value })
+        }
+    }
+
+    //For building addresses in const and static tables. An out of range value fails the build when used in a const
+    #[allow(clippy::absurd_extreme_comparisons)]
+    pub const fn from_const(value: u8) -> ZanCanAddress {
+        assert!(value <= MAX_ADDRESS, "address does not fit in ADDRESS_BIT_LENGTH bits");
+        ZanCanAddress { id: value }
+    }
+}
+
 impl From<u8> for ZanCanAddress {
     fn from(v: u8) -> Self {
         Self { id: v }
@@ -23,14 +64,132 @@ impl From<ZanCanAddress> for u8 {
     }
 }
 
+//Widening conversions for indexing per address tables. Valid addresses are 0 to MAX_ADDRESS, the low ADDRESS_BIT_LENGTH bits.
+//There's no TryFrom<u8> since From<u8> already covers it, ZanCanAddress::new is the checked version
+impl From<ZanCanAddress> for u16 {
+    fn from(addr: ZanCanAddress) -> Self {
+        addr.id as u16
+    }
+}
+
+impl From<ZanCanAddress> for usize {
+    fn from(addr: ZanCanAddress) -> Self {
+        addr.id as usize
+    }
+}
+
+//Values above MAX_ADDRESS don't fit in the address bits of a standard id and give InvalidId
+impl TryFrom<u16> for ZanCanAddress {
+    type Error = ZanCanError;
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        if value > MAX_ADDRESS as u16 {
+            Err(ZanCanError::InvalidId)
+        } else {
+            Ok(ZanCanAddress { id: value as u8 })
+        }
+    }
+}
+
+//Extended ids only convert when their address is small enough to fit, use ExtendedAddress for the rest
 impl TryFrom<Id> for ZanCanAddress {
-    type Error = &'static str;
+    type Error = ZanCanError;
     fn try_from(value: Id) -> Result<Self, Self::Error> {
         match value {
-            Id::Extended(_) => Err("unable to convert from Id to ZanCanAddress due to extended ids not being supported"),
+            Id::Extended(eid) => {
+                let addr = eid.as_raw() & MAX_EXTENDED_ADDRESS;
+                if addr > MAX_ADDRESS as u32 {
+                    Err(ZanCanError::ExtendedIdNotSupported)
+                } else {
+                    Ok(ZanCanAddress { id: addr as u8 })
+                }
+            },
             Id::Standard(sid) => {
-                Ok(ZanCanAddress { id: sid.as_raw() as u8 })
+                Ok(ZanCanAddress { id: (sid.as_raw() & MAX_ADDRESS as u16) as u8 })
             }
         }
     }
-}
\ No newline at end of file
+}
+
+//Wider address carried by frames in extended id mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ExtendedAddress {
+    id: u32
+}
+
+impl ExtendedAddress {
+    pub const BROADCAST: ExtendedAddress = ExtendedAddress { id: 0x00 };
+
+    pub fn is_broadcast(&self) -> bool {
+        *self == ExtendedAddress::BROADCAST
+    }
+
+    //None if the value doesn't fit in EXTENDED_ADDRESS_BIT_LENGTH bits
+    pub fn new(value: u32) -> Option<ExtendedAddress> {
+        if value > MAX_EXTENDED_ADDRESS {
+            None
+        } else {
+            Some(ExtendedAddress { id: value })
+        }
+    }
+
+    pub const fn from_const(value: u32) -> ExtendedAddress {
+        assert!(value <= MAX_EXTENDED_ADDRESS, "address does not fit in EXTENDED_ADDRESS_BIT_LENGTH bits");
+        ExtendedAddress { id: value }
+    }
+}
+
+//Every standard address is also a valid extended address
+impl From<ZanCanAddress> for ExtendedAddress {
+    fn from(addr: ZanCanAddress) -> Self {
+        ExtendedAddress { id: addr.id as u32 }
+    }
+}
+
+impl From<ExtendedAddress> for u32 {
+    fn from(addr: ExtendedAddress) -> Self {
+        addr.id
+    }
+}
+
+impl TryFrom<Id> for ExtendedAddress {
+    type Error = ZanCanError;
+    fn try_from(value: Id) -> Result<Self, Self::Error> {
+        match value {
+            Id::Extended(eid) => Ok(ExtendedAddress { id: eid.as_raw() & MAX_EXTENDED_ADDRESS }),
+            Id::Standard(sid) => Ok(ExtendedAddress { id: (sid.as_raw() & MAX_ADDRESS as u16) as u32 })
+        }
+    }
+}
+
+//Which addresses a gateway or receiver lets through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressFilter {
+    //Every address from low to high, both included
+    Range { low: ZanCanAddress, high: ZanCanAddress },
+    //Addresses whose bits selected by mask equal the same bits of value. A mask of 0 lets everything through
+    Mask { value: u8, mask: u8 }
+}
+
+impl AddressFilter {
+    pub fn range(low: ZanCanAddress, high: ZanCanAddress) -> AddressFilter {
+        AddressFilter::Range { low, high }
+    }
+
+    pub fn mask(value: u8, mask: u8) -> AddressFilter {
+        AddressFilter::Mask { value, mask }
+    }
+
+    pub fn contains(&self, addr: ZanCanAddress) -> bool {
+        match *self {
+            AddressFilter::Range { low, high } => low <= addr && addr <= high,
+            AddressFilter::Mask { value, mask } => addr.id & mask == value & mask
+        }
+    }
+
+    //Goes by the whole address so an extended frame past MAX_ADDRESS never matches on its low byte
+    pub fn matches(&self, frame: &ZanCanFrame) -> bool {
+        let addr = u32::from(frame.extended_address());
+        addr <= MAX_ADDRESS as u32 && self.contains(ZanCanAddress { id: addr as u8 })
+    }
+}
@@ -0,0 +1,91 @@
+
+//Minimal DBC style signal layer for host tools. A signal is a bit range of the payload with a scale and
+//offset, laid out the same way a DBC file describes it, so a message definition read from a DBC can be mapped
+//onto these and applied to a ZanCanFrame without the crate needing a DBC parser
+
+use super::ZanCanFrame;
+
+//Intel is little endian with start_bit as the lowest bit. Motorola is big endian with start_bit as the
+//highest bit, using the DBC sawtooth bit numbering
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteOrder {
+    Intel,
+    Motorola
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Signal {
+    pub name: &'static str,
+    pub start_bit: u8,
+    pub length: u8,
+    pub byte_order: ByteOrder,
+    pub signed: bool,
+    pub factor: f64,
+    pub offset: f64
+}
+
+impl Signal {
+    //Raw integer value, None if the bit range doesn't fit in the 64 bits of a classic frame
+    pub fn raw(&self, frame: &ZanCanFrame) -> Option<i64> {
+        let length = self.length as u32;
+        if length == 0 || length > 64 {
+            return None;
+        }
+        let mut bytes = [0u8; 8];
+        let payload = frame.raw_payload();
+        bytes[..payload.len()].copy_from_slice(payload);
+
+        let shift = match self.byte_order {
+            ByteOrder::Intel => {
+                if self.start_bit as u32 + length > 64 {
+                    return None;
+                }
+                self.start_bit as u32
+            },
+            ByteOrder::Motorola => {
+                //Position of the msb counting from the top of the big endian word
+                let msb = (self.start_bit as u32 / 8) * 8 + (7 - self.start_bit as u32 % 8);
+                if msb + length > 64 {
+                    return None;
+                }
+                64 - (msb + length)
+            }
+        };
+        let word = match self.byte_order {
+            ByteOrder::Intel => u64::from_le_bytes(bytes),
+            ByteOrder::Motorola => u64::from_be_bytes(bytes)
+        };
+        let mask = if length == 64 { u64::MAX } else { (1u64 << length) - 1 };
+        let value = (word >> shift) & mask;
+
+        if self.signed && length < 64 && value & (1u64 << (length - 1)) != 0 {
+            Some((value | !mask) as i64)
+        } else {
+            Some(value as i64)
+        }
+    }
+
+    //Value in engineering units, raw * factor + offset
+    pub fn decode(&self, frame: &ZanCanFrame) -> Option<f64> {
+        self.raw(frame).map(|raw| raw as f64 * self.factor + self.offset)
+    }
+}
+
+//A DBC message, the id it is sent on and the signals inside it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DbcMessage<'a> {
+    pub id: u32,
+    pub signals: &'a [Signal]
+}
+
+impl<'a> DbcMessage<'a> {
+    pub fn matches(&self, frame: &ZanCanFrame) -> bool {
+        frame.raw_id() == self.id
+    }
+
+    //Every signal by name. Frames on other ids give nothing
+    pub fn decode<'f>(&'f self, frame: &'f ZanCanFrame) -> impl Iterator<Item = (&'static str, f64)> + 'f {
+        let matches = self.matches(frame);
+        self.signals.iter().filter(move |_| matches).filter_map(move |s| s.decode(frame).map(|v| (s.name, v)))
+    }
+}
@@ -0,0 +1,54 @@
+
+//Drops the exact retransmits a noisy bus produces. Only the last frame per sender and type is kept, so a retransmit
+//that arrives after N other pairs have been seen is let through again
+
+use super::ZanCanFrame;
+
+//Remembers the last frame from each (type, address) pair. N is how many pairs are remembered at once,
+//once full the oldest pair is forgotten to make room
+pub struct DedupFilter<const N: usize> {
+    last: [Option<ZanCanFrame>; N],
+    next_evict: usize
+}
+
+impl<const N: usize> DedupFilter<N> {
+    pub fn new() -> Self {
+        DedupFilter { last: [None; N], next_evict: 0 }
+    }
+
+    //True if the frame says the same thing as the previous one of its type from the same sender.
+    //Compared with semantically_eq so bytes past the dlc don't matter. The frame is remembered either way
+    pub fn is_duplicate(&mut self, frame: &ZanCanFrame) -> bool {
+        let same_key = |f: &ZanCanFrame| f.frame_type() == frame.frame_type() && f.extended_address() == frame.extended_address();
+
+        if let Some(slot) = self.last.iter_mut().flatten().find(|f| same_key(f)) {
+            let duplicate = slot.semantically_eq(frame);
+            *slot = *frame;
+            return duplicate;
+        }
+
+        if N > 0 {
+            let i = match self.last.iter().position(|f| f.is_none()) {
+                Some(free) => free,
+                None => {
+                    let oldest = self.next_evict;
+                    self.next_evict = (self.next_evict + 1) % N;
+                    oldest
+                }
+            };
+            self.last[i] = Some(*frame);
+        }
+        false
+    }
+
+    pub fn clear(&mut self) {
+        self.last = [None; N];
+        self.next_evict = 0;
+    }
+}
+
+impl<const N: usize> Default for DedupFilter<N> {
+    fn default() -> Self {
+        DedupFilter::new()
+    }
+}
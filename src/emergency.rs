@@ -0,0 +1,220 @@
+
+use super::error::{ErrorCode, ZanCanError};
+
+//Status is carried in the top bit of the first data byte, the reason takes the remaining 15 bits
+pub const STATUS_BIT: u8 = 0x80;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EmergencyStatus {
+    //Status bit 0, the emergency is in effect and everything should be stopped
+    Stop,
+    //Status bit 1, the emergency is over
+    Clear
+}
+
+impl EmergencyStatus {
+    pub fn is_active(&self) -> bool {
+        *self == EmergencyStatus::Stop
+    }
+
+    pub fn is_cleared(&self) -> bool {
+        *self == EmergencyStatus::Clear
+    }
+}
+
+impl From<EmergencyStatus> for u8 {
+    fn from(s: EmergencyStatus) -> u8 {
+        match s {
+            EmergencyStatus::Stop => 0x00,
+            EmergencyStatus::Clear => STATUS_BIT
+        }
+    }
+}
+
+//Expects the status bit still in position, so valid values are 0x00 and STATUS_BIT
+impl TryFrom<u8> for EmergencyStatus {
+    type Error = ZanCanError;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0x00 => Ok(EmergencyStatus::Stop),
+            STATUS_BIT => Ok(EmergencyStatus::Clear),
+            _ => Err(ZanCanError::InvalidEmergencyStatus)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(into = "u16"))]
+pub struct EmergencyReason {
+    pub reason: u16
+}
+
+impl EmergencyReason {
+    //Operator hit a stop button
+    pub const MANUAL_STOP: EmergencyReason = EmergencyReason { reason: 0x0001 };
+    pub const UNDER_VOLTAGE: EmergencyReason = EmergencyReason { reason: 0x0101 };
+    pub const OVER_VOLTAGE: EmergencyReason = EmergencyReason { reason: 0x0102 };
+    pub const OVER_CURRENT: EmergencyReason = EmergencyReason { reason: 0x0103 };
+    pub const OVER_TEMPERATURE: EmergencyReason = EmergencyReason { reason: 0x0201 };
+    pub const BUS_OFF: EmergencyReason = EmergencyReason { reason: 0x0301 };
+    //A node stopped sending heartbeats
+    pub const HEARTBEAT_LOST: EmergencyReason = EmergencyReason { reason: 0x0302 };
+    //Something is in the way, like a derailed car on the track
+    pub const OBSTRUCTION: EmergencyReason = EmergencyReason { reason: 0x0401 };
+
+    //Every reason this library has a name for. Any other 15 bit value is still a valid reason
+    pub const ALL: [EmergencyReason; 8] = [
+        EmergencyReason::MANUAL_STOP,
+        EmergencyReason::UNDER_VOLTAGE,
+        EmergencyReason::OVER_VOLTAGE,
+        EmergencyReason::OVER_CURRENT,
+        EmergencyReason::OVER_TEMPERATURE,
+        EmergencyReason::BUS_OFF,
+        EmergencyReason::HEARTBEAT_LOST,
+        EmergencyReason::OBSTRUCTION
+    ];
+
+    pub fn all() -> impl Iterator<Item = EmergencyReason> {
+        EmergencyReason::ALL.into_iter()
+    }
+
+    pub fn is_known(&self) -> bool {
+        EmergencyReason::ALL.contains(self)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            EmergencyReason::MANUAL_STOP => "ManualStop",
+            EmergencyReason::UNDER_VOLTAGE => "UnderVoltage",
+            EmergencyReason::OVER_VOLTAGE => "OverVoltage",
+            EmergencyReason::OVER_CURRENT => "OverCurrent",
+            EmergencyReason::OVER_TEMPERATURE => "OverTemperature",
+            EmergencyReason::BUS_OFF => "BusOff",
+            EmergencyReason::HEARTBEAT_LOST => "HeartbeatLost",
+            EmergencyReason::OBSTRUCTION => "Obstruction",
+            _ => "Unknown"
+        }
+    }
+}
+
+//How bad an emergency is, ordered from least to most severe.
+//Info only needs logging, Warning should slow or pause things and Critical means halt all motion
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EmergencySeverity {
+    Info,
+    Warning,
+    Critical
+}
+
+impl EmergencyReason {
+    //Only the reasons that can wait are listed, everything else including reasons this library doesn't know is Critical to be safe
+    pub fn severity(&self) -> EmergencySeverity {
+        match *self {
+            EmergencyReason::UNDER_VOLTAGE => EmergencySeverity::Warning,
+            EmergencyReason::OVER_TEMPERATURE => EmergencySeverity::Warning,
+            EmergencyReason::HEARTBEAT_LOST => EmergencySeverity::Warning,
+            _ => EmergencySeverity::Critical
+        }
+    }
+
+    //Grouped by the high byte the same way error codes are, so reasons this library doesn't know still land
+    //in a group as long as they follow the numbering
+    pub fn subsystem(&self) -> Subsystem {
+        match self.reason >> 8 {
+            0x00 => Subsystem::Operator,
+            0x01 => Subsystem::Power,
+            0x02 => Subsystem::Thermal,
+            0x03 => Subsystem::Communication,
+            0x04 => Subsystem::Mechanical,
+            _ => Subsystem::Other
+        }
+    }
+
+    //Short instruction for operator screens so every tool gives the same guidance.
+    //Reasons this library doesn't know get a generic instruction to go look at the node
+    pub fn recommended_action(&self) -> &'static str {
+        match *self {
+            EmergencyReason::MANUAL_STOP => "Find out why the stop was pressed before releasing it",
+            EmergencyReason::UNDER_VOLTAGE => "Check the power supply and wiring",
+            EmergencyReason::OVER_VOLTAGE => "Disconnect power and check the supply voltage",
+            EmergencyReason::OVER_CURRENT => "Disconnect power and check for a short circuit",
+            EmergencyReason::OVER_TEMPERATURE => "Check cooling fan and let the node cool down",
+            EmergencyReason::BUS_OFF => "Check bus wiring and termination",
+            EmergencyReason::HEARTBEAT_LOST => "Check that the node is powered and connected",
+            EmergencyReason::OBSTRUCTION => "Clear the obstruction",
+            _ => "Inspect the node that raised the emergency"
+        }
+    }
+}
+
+//Part of the system an emergency reason comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Subsystem {
+    //A person asked for the stop
+    Operator,
+    Power,
+    Thermal,
+    Communication,
+    //Moving parts and whatever they run into
+    Mechanical,
+    Other
+}
+
+//Error codes that can be escalated to an emergency. Codes without a counterpart give NoEquivalent.
+//Goes by value like ErrorCode::name so a hand built Unknown holding a known code still converts
+impl TryFrom<ErrorCode> for EmergencyReason {
+    type Error = ZanCanError;
+    fn try_from(code: ErrorCode) -> Result<Self, Self::Error> {
+        match ErrorCode::from(u16::from(code)) {
+            ErrorCode::UnderVoltage => Ok(EmergencyReason::UNDER_VOLTAGE),
+            ErrorCode::OverVoltage => Ok(EmergencyReason::OVER_VOLTAGE),
+            ErrorCode::OverCurrent => Ok(EmergencyReason::OVER_CURRENT),
+            ErrorCode::OverTemperature => Ok(EmergencyReason::OVER_TEMPERATURE),
+            ErrorCode::BusOff => Ok(EmergencyReason::BUS_OFF),
+            //A lost heartbeat is the emergency form of a message timing out
+            ErrorCode::MessageTimeout => Ok(EmergencyReason::HEARTBEAT_LOST),
+            _ => Err(ZanCanError::NoEquivalent)
+        }
+    }
+}
+
+//Reverse of the above. Manual stops and obstructions only exist as emergencies
+impl TryFrom<EmergencyReason> for ErrorCode {
+    type Error = ZanCanError;
+    fn try_from(reason: EmergencyReason) -> Result<Self, Self::Error> {
+        match reason {
+            EmergencyReason::UNDER_VOLTAGE => Ok(ErrorCode::UnderVoltage),
+            EmergencyReason::OVER_VOLTAGE => Ok(ErrorCode::OverVoltage),
+            EmergencyReason::OVER_CURRENT => Ok(ErrorCode::OverCurrent),
+            EmergencyReason::OVER_TEMPERATURE => Ok(ErrorCode::OverTemperature),
+            EmergencyReason::BUS_OFF => Ok(ErrorCode::BusOff),
+            EmergencyReason::HEARTBEAT_LOST => Ok(ErrorCode::MessageTimeout),
+            _ => Err(ZanCanError::NoEquivalent)
+        }
+    }
+}
+
+//Reason has to fit in 15 bits
+impl TryFrom<u16> for EmergencyReason {
+    type Error = ZanCanError;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        if value >= 0x8000u16 {
+            Err(ZanCanError::InvalidReason)
+        } else {
+            Ok(EmergencyReason { reason: value })
+        }
+    }
+}
+
+impl From<EmergencyReason> for u16 {
+    fn from(value: EmergencyReason) -> Self {
+        value.reason
+    }
+}
\ No newline at end of file
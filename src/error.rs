@@ -1,2 +1,181 @@
 
-pub type ErrorCode = u16;
+use core::fmt;
+
+use super::zan_can_type::ZanCanFrameType;
+
+//Optional third byte of an Error frame
+pub const ERROR_RAISED: u8 = 0x00;
+pub const ERROR_CLEARED: u8 = 0x01;
+
+//Error code a device reports on the bus in an Error frame. High byte is the category, low byte the detail.
+//Codes this library doesn't know about are kept as Unknown so the raw value is never lost
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(from = "u16", into = "u16"))]
+pub enum ErrorCode {
+    UnderVoltage,
+    OverVoltage,
+    OverCurrent,
+    OverTemperature,
+    UnderTemperature,
+    BusOff,
+    MessageTimeout,
+    SensorFault,
+    ActuatorFault,
+    Unknown(u16)
+}
+
+impl ErrorCode {
+    //Every known code. Unknown isn't included since it covers everything else
+    pub const ALL: [ErrorCode; 9] = [
+        ErrorCode::UnderVoltage,
+        ErrorCode::OverVoltage,
+        ErrorCode::OverCurrent,
+        ErrorCode::OverTemperature,
+        ErrorCode::UnderTemperature,
+        ErrorCode::BusOff,
+        ErrorCode::MessageTimeout,
+        ErrorCode::SensorFault,
+        ErrorCode::ActuatorFault
+    ];
+
+    pub fn all() -> impl Iterator<Item = ErrorCode> {
+        ErrorCode::ALL.into_iter()
+    }
+
+    pub fn as_u16(&self) -> u16 {
+        u16::from(*self)
+    }
+
+    //High byte, groups related codes together
+    pub fn category(&self) -> u8 {
+        (self.as_u16() >> 8) as u8
+    }
+
+    //Low byte, which specific error within the category
+    pub fn detail(&self) -> u8 {
+        self.as_u16() as u8
+    }
+
+    pub fn is_known(&self) -> bool {
+        !matches!(ErrorCode::from(u16::from(*self)), ErrorCode::Unknown(_))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match ErrorCode::from(u16::from(*self)) {
+            ErrorCode::UnderVoltage => "UnderVoltage",
+            ErrorCode::OverVoltage => "OverVoltage",
+            ErrorCode::OverCurrent => "OverCurrent",
+            ErrorCode::OverTemperature => "OverTemperature",
+            ErrorCode::UnderTemperature => "UnderTemperature",
+            ErrorCode::BusOff => "BusOff",
+            ErrorCode::MessageTimeout => "MessageTimeout",
+            ErrorCode::SensorFault => "SensorFault",
+            ErrorCode::ActuatorFault => "ActuatorFault",
+            ErrorCode::Unknown(_) => "Unknown"
+        }
+    }
+}
+
+impl From<u16> for ErrorCode {
+    fn from(value: u16) -> Self {
+        match value {
+            0x0101 => ErrorCode::UnderVoltage,
+            0x0102 => ErrorCode::OverVoltage,
+            0x0103 => ErrorCode::OverCurrent,
+            0x0201 => ErrorCode::OverTemperature,
+            0x0202 => ErrorCode::UnderTemperature,
+            0x0301 => ErrorCode::BusOff,
+            0x0302 => ErrorCode::MessageTimeout,
+            0x0401 => ErrorCode::SensorFault,
+            0x0402 => ErrorCode::ActuatorFault,
+            _ => ErrorCode::Unknown(value)
+        }
+    }
+}
+
+impl From<ErrorCode> for u16 {
+    fn from(code: ErrorCode) -> Self {
+        match code {
+            ErrorCode::UnderVoltage => 0x0101,
+            ErrorCode::OverVoltage => 0x0102,
+            ErrorCode::OverCurrent => 0x0103,
+            ErrorCode::OverTemperature => 0x0201,
+            ErrorCode::UnderTemperature => 0x0202,
+            ErrorCode::BusOff => 0x0301,
+            ErrorCode::MessageTimeout => 0x0302,
+            ErrorCode::SensorFault => 0x0401,
+            ErrorCode::ActuatorFault => 0x0402,
+            ErrorCode::Unknown(value) => value
+        }
+    }
+}
+
+//Compared by value so Unknown(0x0201) is the same code as OverTemperature
+impl PartialEq for ErrorCode {
+    fn eq(&self, other: &Self) -> bool {
+        u16::from(*self) == u16::from(*other)
+    }
+}
+
+impl Eq for ErrorCode {}
+
+//Errors produced by this library while building or decoding frames. Not to be confused with ErrorCode which is what a device puts on the bus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZanCanError {
+    WrongFrameType { expected: ZanCanFrameType, found: ZanCanFrameType },
+    UnsupportedFrameType(ZanCanFrameType),
+    UnknownFrameType(u8),
+    InvalidEmergencyStatus,
+    InvalidErrorStatus,
+    InvalidReason,
+    InvalidDataIdentifier,
+    InvalidId,
+    ExtendedIdNotSupported,
+    RemoteFrameNotSupported,
+    TruncatedData,
+    //A byte stream ran out part way through a frame, not an error in the frame itself
+    NeedMoreData,
+    OversizedData,
+    //Fits in the frame buffer but isn't a length a CAN-FD frame can carry, see ZanCanFrame::is_valid_data_len
+    InvalidDataLength,
+    PayloadTooLarge,
+    BufferTooSmall,
+    SegmentOutOfOrder,
+    ChecksumMismatch,
+    InvalidCommand,
+    QueueFull,
+    NoEquivalent,
+    TypeIdMismatch { id_type: ZanCanFrameType, stored: ZanCanFrameType }
+}
+
+impl fmt::Display for ZanCanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZanCanError::WrongFrameType { expected, found } => write!(f, "expected {:?} frame, found {:?}", expected, found),
+            ZanCanError::UnsupportedFrameType(t) => write!(f, "{:?} frames are not supported here", t),
+            ZanCanError::UnknownFrameType(code) => write!(f, "unknown frame type code {:#x}", code),
+            ZanCanError::InvalidEmergencyStatus => write!(f, "invalid emergency status"),
+            ZanCanError::InvalidErrorStatus => write!(f, "invalid error status"),
+            ZanCanError::InvalidReason => write!(f, "emergency reason does not fit in 15 bits"),
+            ZanCanError::InvalidDataIdentifier => write!(f, "data identifier does not fit in 30 bits"),
+            ZanCanError::InvalidId => write!(f, "id is out of range for its CAN id width"),
+            ZanCanError::ExtendedIdNotSupported => write!(f, "extended ids are not supported here"),
+            ZanCanError::RemoteFrameNotSupported => write!(f, "remote frames are not supported here"),
+            ZanCanError::TruncatedData => write!(f, "frame data is too short"),
+            ZanCanError::NeedMoreData => write!(f, "need more bytes to finish the frame"),
+            ZanCanError::OversizedData => write!(f, "frame data is longer than 8 bytes"),
+            ZanCanError::InvalidDataLength => write!(f, "frame data length is not a valid CAN-FD length"),
+            ZanCanError::PayloadTooLarge => write!(f, "payload is too large for the message"),
+            ZanCanError::BufferTooSmall => write!(f, "buffer is too small"),
+            ZanCanError::SegmentOutOfOrder => write!(f, "segment arrived out of order"),
+            ZanCanError::ChecksumMismatch => write!(f, "checksum does not match the data"),
+            ZanCanError::InvalidCommand => write!(f, "invalid set command"),
+            ZanCanError::QueueFull => write!(f, "queue is full"),
+            ZanCanError::NoEquivalent => write!(f, "no equivalent value to convert to"),
+            ZanCanError::TypeIdMismatch { id_type, stored } => write!(f, "id holds a {:?} frame but the frame says {:?}", id_type, stored)
+        }
+    }
+}
+
+impl core::error::Error for ZanCanError {}
@@ -0,0 +1,90 @@
+
+//Turns a node's repeated errors into an emergency. The caller passes the time in since this library has no clock,
+//and that time is also what frees up counts that have gone quiet
+
+use super::address::ZanCanAddress;
+use super::emergency::EmergencyReason;
+use super::error::{ErrorCode, ZanCanError};
+use super::ZanCanFrame;
+use embedded_can::Frame;
+
+#[derive(Clone, Copy)]
+struct Entry {
+    addr: ZanCanAddress,
+    code: ErrorCode,
+    count: u8,
+    first_ms: u32,
+    escalated: bool
+}
+
+//Escalates once the same error code is raised threshold times from one node within window_ms.
+//Only codes with an EmergencyReason equivalent can escalate, see TryFrom<ErrorCode> for EmergencyReason.
+//N is how many (address, code) pairs are counted at once
+pub struct EscalationPolicy<const N: usize> {
+    threshold: u8,
+    window_ms: u32,
+    entries: [Option<Entry>; N]
+}
+
+impl<const N: usize> EscalationPolicy<N> {
+    pub fn new(threshold: u8, window_ms: u32) -> Self {
+        EscalationPolicy { threshold, window_ms, entries: [None; N] }
+    }
+
+    //Feeds an error frame in with the time it arrived. A cleared error drops its count.
+    //Extended frames whose address doesn't fit in a ZanCanAddress give ExtendedIdNotSupported.
+    //Errors if the frame isn't a valid error frame or there's no room to count another code. Counts whose window ran out
+    //make room unless they still have an escalation waiting for poll
+    pub fn record(&mut self, frame: &ZanCanFrame, now_ms: u32) -> Result<(), ZanCanError> {
+        let (code, cleared) = frame.decode_error_status()?;
+        let addr = ZanCanAddress::try_from(frame.id())?;
+        let existing = self.entries.iter().position(|e| matches!(e, Some(e) if e.addr == addr && e.code == code));
+
+        if cleared {
+            if let Some(i) = existing {
+                self.entries[i] = None;
+            }
+            return Ok(());
+        }
+        if EmergencyReason::try_from(code).is_err() {
+            return Ok(());
+        }
+
+        let i = match existing {
+            Some(i) => i,
+            None => {
+                //A free slot, or else one whose window ran out with nothing left to poll
+                let i = self.entries.iter().position(|e| e.is_none())
+                    .or_else(|| self.entries.iter().position(|e| matches!(e, Some(e) if !e.escalated && now_ms.wrapping_sub(e.first_ms) > self.window_ms)))
+                    .ok_or(ZanCanError::BufferTooSmall)?;
+                self.entries[i] = None;
+                i
+            }
+        };
+        let entry = self.entries[i].get_or_insert(Entry { addr, code, count: 0, first_ms: now_ms, escalated: false });
+        if now_ms.wrapping_sub(entry.first_ms) > self.window_ms {
+            //Window ran out, this error starts a new one
+            entry.count = 0;
+            entry.first_ms = now_ms;
+        }
+        entry.count = entry.count.saturating_add(1);
+        if entry.count >= self.threshold {
+            entry.escalated = true;
+            entry.count = 0;
+            entry.first_ms = now_ms;
+        }
+        Ok(())
+    }
+
+    //Next emergency to send, if any. Each escalation is given out once
+    pub fn poll(&mut self) -> Option<(ZanCanAddress, EmergencyReason)> {
+        let entry = self.entries.iter_mut().flatten().find(|e| e.escalated)?;
+        entry.escalated = false;
+        let reason = EmergencyReason::try_from(entry.code).expect("only codes with an equivalent reason are counted");
+        Some((entry.addr, reason))
+    }
+
+    pub fn reset(&mut self) {
+        self.entries = [None; N];
+    }
+}
@@ -0,0 +1,77 @@
+
+//Single line JSON for host side log ingestion. The payload is decoded by frame type so logs get field names
+//instead of raw bytes. Writes through fmt::Write so a String works and so does a fixed buffer on a device
+
+use core::fmt::{self, Write};
+
+use embedded_can::Frame;
+
+use super::emergency::EmergencyStatus;
+use super::zan_can_type::ZanCanFrameType;
+use super::ZanCanFrame;
+
+impl<const N: usize> ZanCanFrame<N> {
+    //e.g. {"type":"Emergency","addr":18,"status":"Stop","reason":"OverTemperature","reason_code":513}.
+    //Frames that don't decode, and Time frames, give their raw bytes as "data"
+    pub fn to_json<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write!(out, "{{\"type\":\"{}\",\"addr\":{}", self.frame_type().name(), u32::from(self.extended_address()))?;
+        if self.is_remote_frame() {
+            return write!(out, ",\"remote\":true,\"dlc\":{}}}", self.dlc());
+        }
+
+        match self.frame_type() {
+            ZanCanFrameType::Emergency => match self.decode_emergency() {
+                Ok((status, reason)) => {
+                    let status = match status {
+                        EmergencyStatus::Stop => "Stop",
+                        EmergencyStatus::Clear => "Clear"
+                    };
+                    write!(out, ",\"status\":\"{}\",\"reason\":\"{}\",\"reason_code\":{}", status, reason.name(), u16::from(reason))?;
+                },
+                Err(_) => write_bytes(out, "data", self.raw_payload())?
+            },
+            ZanCanFrameType::Error => match self.decode_error_status() {
+                Ok((code, cleared)) => write!(out, ",\"code\":\"{}\",\"code_value\":{},\"cleared\":{}", code.name(), u16::from(code), cleared)?,
+                Err(_) => write_bytes(out, "data", self.raw_payload())?
+            },
+            ZanCanFrameType::SentData | ZanCanFrameType::SetData => match self.data_identifier() {
+                Ok(data_id) => {
+                    write!(out, ",\"identifier\":{}", u32::from(data_id))?;
+                    write_bytes(out, "data", self.data_payload())?;
+                },
+                Err(_) => write_bytes(out, "data", self.raw_payload())?
+            },
+            ZanCanFrameType::RequestData => match self.decode_request_data_with_args() {
+                Ok((data_id, args)) => {
+                    write!(out, ",\"identifier\":{}", u32::from(data_id))?;
+                    write_bytes(out, "args", args)?;
+                },
+                Err(_) => write_bytes(out, "data", self.raw_payload())?
+            },
+            ZanCanFrameType::DataNak => match self.decode_data_nak() {
+                Ok((data_id, reason)) => write!(out, ",\"identifier\":{},\"reason\":{}", u32::from(data_id), u8::from(reason))?,
+                Err(_) => write_bytes(out, "data", self.raw_payload())?
+            },
+            ZanCanFrameType::Heartbeat => match self.decode_heartbeat() {
+                Ok(uptime_ms) => write!(out, ",\"uptime_ms\":{}", uptime_ms)?,
+                Err(_) => write_bytes(out, "data", self.raw_payload())?
+            },
+            ZanCanFrameType::Time => write_bytes(out, "data", self.raw_payload())?
+        }
+        out.write_char('}')
+    }
+}
+
+//,"name":[1,2,3]
+fn write_bytes<W: Write>(out: &mut W, name: &str, bytes: &[u8]) -> fmt::Result {
+    write!(out, ",\"{}\":[", name)?;
+    let mut i: usize = 0;
+    while i < bytes.len() {
+        if i > 0 {
+            out.write_char(',')?;
+        }
+        write!(out, "{}", bytes[i])?;
+        i += 1;
+    }
+    out.write_char(']')
+}
@@ -3,41 +3,268 @@
 This file is meant as an import of other files in library
  */
 
-use embedded_can::{Frame, Id, StandardId};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+use embedded_can::{ExtendedId, Frame, Id, StandardId};
 
 pub mod address;
 pub mod zan_can_type;
-pub mod emegency;
+pub mod emergency;
+//Old misspelled name kept so existing code still builds while it moves over to emergency
+#[deprecated(note = "renamed to emergency")]
+pub mod emegency {
+    pub use super::emergency::*;
+
+    #[deprecated(note = "renamed to EmergencyStatus")]
+    pub type EmegencyStatus = super::emergency::EmergencyStatus;
+}
+pub mod dedup;
 pub mod error;
+pub mod escalation;
 pub mod message_data;
+pub mod message;
+pub mod matcher;
+pub mod node;
+#[cfg(any(test, feature = "test-util"))]
+pub mod parse;
+pub mod protected;
+pub mod segment;
+pub mod stats;
+pub mod timed;
+pub mod tracker;
+pub mod transmit;
+pub mod tx_queue;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "dbc")]
+pub mod dbc;
+#[cfg(feature = "json")]
+mod json;
 
 use zan_can_type::ZanCanFrameType;
-use address::ZanCanAddress;
-use emegency::{EmegencyStatus, EmergencyReason};
-use message_data::{DataIdentifier, DataMessage};
+use address::{ExtendedAddress, ZanCanAddress};
+use emergency::{EmergencyStatus, EmergencyReason};
+use message_data::{DataIdentifier, DataMessage, NakReason};
+use error::ZanCanError;
+use message::ZanCanMessage;
 
-pub struct ZanCanFrame {
+//N is the size of the data buffer. It defaults to 8 for classic CAN, 64 holds a CAN-FD frame
+#[derive(Clone, Copy)]
+pub struct ZanCanFrame<const N: usize = 8> {
     id: Id,
     data_len: usize,
-    data: [u8; 8],
-    f_type: ZanCanFrameType
+    data: [u8; N],
+    f_type: ZanCanFrameType,
+    //Some for a classic RTR frame, holding the dlc it asked for. Remote frames carry no data so data_len is 0
+    remote_dlc: Option<u8>
+}
+
+//Bytes past data_len are left over from whatever built the frame so they don't take part in equality
+impl<const N: usize> PartialEq for ZanCanFrame<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.f_type == other.f_type && self.data() == other.data() && self.remote_dlc == other.remote_dlc
+    }
+}
+
+impl<const N: usize> Eq for ZanCanFrame<N> {}
+
+//Has to agree with PartialEq so only the significant bytes are hashed
+impl<const N: usize> Hash for ZanCanFrame<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.f_type.hash(state);
+        self.data().hash(state);
+        self.remote_dlc.hash(state);
+    }
+}
+
+impl<const N: usize> fmt::Debug for ZanCanFrame<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_struct("ZanCanFrame");
+        d.field("type_code", &format_args!("{:#05b}", self.type_code()));
+        d.field("address", &format_args!("{:#04x}", u32::from(self.extended_address())));
+        d.field("frame_type", &self.f_type);
+        d.field("dlc", &self.dlc());
+        if self.remote_dlc.is_some() {
+            d.field("remote", &true);
+        }
+
+        //Show the decoded payload where we know how to, otherwise fall back to the raw bytes
+        match self.f_type {
+            ZanCanFrameType::Emergency => match self.decode_emergency() {
+                Ok((status, reason)) => d.field("status", &status).field("reason", &reason),
+                Err(_) => d.field("data", &HexBytes(self.data()))
+            },
+            ZanCanFrameType::Error => match self.decode_error_status() {
+                Ok((code, cleared)) => d.field("code", &code).field("cleared", &cleared),
+                Err(_) => d.field("data", &HexBytes(self.data()))
+            },
+            ZanCanFrameType::SentData => match self.decode_sent_data() {
+                Ok(message) => d.field("message", &message),
+                Err(_) => d.field("data", &HexBytes(self.data()))
+            },
+            ZanCanFrameType::RequestData => match self.decode_request_data() {
+                Ok(data_id) => d.field("data_identifier", &data_id),
+                Err(_) => d.field("data", &HexBytes(self.data()))
+            },
+            ZanCanFrameType::DataNak => match self.decode_data_nak() {
+                Ok((data_id, reason)) => d.field("data_identifier", &data_id).field("reason", &reason),
+                Err(_) => d.field("data", &HexBytes(self.data()))
+            },
+            ZanCanFrameType::SetData => match self.decode_set_data() {
+                Ok(message) => d.field("message", &message),
+                Err(_) => d.field("data", &HexBytes(self.data()))
+            },
+            ZanCanFrameType::Heartbeat => match self.decode_heartbeat() {
+                Ok(uptime_ms) => d.field("uptime_ms", &uptime_ms),
+                Err(_) => d.field("data", &HexBytes(self.data()))
+            },
+            _ => d.field("data", &HexBytes(self.data()))
+        };
+        d.finish()
+    }
+}
+
+//Compact single line for operator screens and serial consoles, e.g. EMERGENCY node=0x12 status=Stop reason=OverTemperature
+impl<const N: usize> fmt::Display for ZanCanFrame<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.f_type {
+            ZanCanFrameType::Emergency => "EMERGENCY",
+            ZanCanFrameType::DataNak => "DATA_NAK",
+            ZanCanFrameType::Error => "ERROR",
+            ZanCanFrameType::Time => "TIME",
+            ZanCanFrameType::SentData => "SENT_DATA",
+            ZanCanFrameType::RequestData => "REQUEST_DATA",
+            ZanCanFrameType::SetData => "SET_DATA",
+            ZanCanFrameType::Heartbeat => "HEARTBEAT"
+        };
+        write!(f, "{} node={:#04x}", label, u32::from(self.extended_address()))?;
+
+        //Data frames only need the identifier and payload split so skip building a DataMessage
+        if let (ZanCanFrameType::SentData | ZanCanFrameType::SetData, Ok(data_id)) = (self.f_type, self.data_identifier()) {
+            write!(f, " id={:#x} data=", u32::from(data_id))?;
+            return write_hex_list(f, self.data_payload());
+        }
+
+        match self.decode() {
+            Ok(ZanCanMessage::Emergency(status, reason)) => {
+                write!(f, " status={:?} reason=", status)?;
+                if reason.is_known() {
+                    write!(f, "{}", reason.name())
+                } else {
+                    write!(f, "{:#06x}", u16::from(reason))
+                }
+            },
+            Ok(ZanCanMessage::Error(code)) => write_error_code(f, code),
+            Ok(ZanCanMessage::ErrorCleared(code)) => {
+                write_error_code(f, code)?;
+                write!(f, " cleared")
+            },
+            Ok(ZanCanMessage::RequestData(data_id)) => write!(f, " id={:#x}", u32::from(data_id)),
+            Ok(ZanCanMessage::DataNak(data_id, reason)) => write!(f, " id={:#x} reason={:?}", u32::from(data_id), reason),
+            Ok(ZanCanMessage::Heartbeat(uptime_ms)) => write!(f, " uptime_ms={}", uptime_ms),
+            _ => {
+                write!(f, " data=")?;
+                write_hex_list(f, self.data())
+            }
+        }
+    }
+}
+
+fn write_error_code(f: &mut fmt::Formatter<'_>, code: error::ErrorCode) -> fmt::Result {
+    if code.is_known() {
+        write!(f, " code={}", code.name())
+    } else {
+        write!(f, " code={:#06x}", u16::from(code))
+    }
+}
+
+//Space separated hex bytes without the 0x prefix
+fn write_hex_list(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    let mut first = true;
+    for b in bytes {
+        if !first {
+            write!(f, " ")?;
+        }
+        write!(f, "{:02x}", b)?;
+        first = false;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "defmt")]
+impl<const N: usize> defmt::Format for ZanCanFrame<N> {
+    fn format(&self, f: defmt::Formatter) {
+        let addr = u32::from(self.extended_address());
+        match self.f_type {
+            ZanCanFrameType::Emergency => match self.decode_emergency() {
+                Ok((status, reason)) => defmt::write!(f, "ZanCanFrame {{ frame_type: {}, address: {=u32:#x}, status: {}, reason: {} }}", self.f_type, addr, status, reason),
+                Err(_) => defmt::write!(f, "ZanCanFrame {{ frame_type: {}, address: {=u32:#x}, data: {=[u8]:#x} }}", self.f_type, addr, self.data())
+            },
+            ZanCanFrameType::Error => match self.decode_error_status() {
+                Ok((code, cleared)) => defmt::write!(f, "ZanCanFrame {{ frame_type: {}, address: {=u32:#x}, code: {}, cleared: {=bool} }}", self.f_type, addr, code, cleared),
+                Err(_) => defmt::write!(f, "ZanCanFrame {{ frame_type: {}, address: {=u32:#x}, data: {=[u8]:#x} }}", self.f_type, addr, self.data())
+            },
+            ZanCanFrameType::SentData => match self.decode_sent_data() {
+                Ok(message) => defmt::write!(f, "ZanCanFrame {{ frame_type: {}, address: {=u32:#x}, message: {} }}", self.f_type, addr, message),
+                Err(_) => defmt::write!(f, "ZanCanFrame {{ frame_type: {}, address: {=u32:#x}, data: {=[u8]:#x} }}", self.f_type, addr, self.data())
+            },
+            ZanCanFrameType::SetData => match self.decode_set_data() {
+                Ok(message) => defmt::write!(f, "ZanCanFrame {{ frame_type: {}, address: {=u32:#x}, message: {} }}", self.f_type, addr, message),
+                Err(_) => defmt::write!(f, "ZanCanFrame {{ frame_type: {}, address: {=u32:#x}, data: {=[u8]:#x} }}", self.f_type, addr, self.data())
+            },
+            ZanCanFrameType::RequestData => match self.decode_request_data() {
+                Ok(data_id) => defmt::write!(f, "ZanCanFrame {{ frame_type: {}, address: {=u32:#x}, data_identifier: {} }}", self.f_type, addr, data_id),
+                Err(_) => defmt::write!(f, "ZanCanFrame {{ frame_type: {}, address: {=u32:#x}, data: {=[u8]:#x} }}", self.f_type, addr, self.data())
+            },
+            ZanCanFrameType::DataNak => match self.decode_data_nak() {
+                Ok((data_id, reason)) => defmt::write!(f, "ZanCanFrame {{ frame_type: {}, address: {=u32:#x}, data_identifier: {}, reason: {} }}", self.f_type, addr, data_id, reason),
+                Err(_) => defmt::write!(f, "ZanCanFrame {{ frame_type: {}, address: {=u32:#x}, data: {=[u8]:#x} }}", self.f_type, addr, self.data())
+            },
+            ZanCanFrameType::Heartbeat => match self.decode_heartbeat() {
+                Ok(uptime_ms) => defmt::write!(f, "ZanCanFrame {{ frame_type: {}, address: {=u32:#x}, uptime_ms: {=u32} }}", self.f_type, addr, uptime_ms),
+                Err(_) => defmt::write!(f, "ZanCanFrame {{ frame_type: {}, address: {=u32:#x}, data: {=[u8]:#x} }}", self.f_type, addr, self.data())
+            },
+            _ => defmt::write!(f, "ZanCanFrame {{ frame_type: {}, address: {=u32:#x}, data: {=[u8]:#x} }}", self.f_type, addr, self.data())
+        }
+    }
+}
+
+//Formats a byte slice as a list of hex values for Debug output
+struct HexBytes<'a>(&'a [u8]);
+
+impl fmt::Debug for HexBytes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut l = f.debug_list();
+        for b in self.0 {
+            l.entry(&format_args!("{:#04x}", b));
+        }
+        l.finish()
+    }
 }
 
-impl Frame for ZanCanFrame {
-    fn new(_: impl Into<Id>, _: &[u8]) -> Option<Self> {
-        panic!("new is intentionally not implemented for ZanCanFrame since it doesn't make sense")
+impl<const N: usize> Frame for ZanCanFrame<N> {
+    fn new(id: impl Into<Id>, data: &[u8]) -> Option<Self> {
+        //Either id width works as long as it holds a known type code
+        Self::from_id(id.into(), data).ok()
     }
 
-    fn new_remote(_: impl Into<Id>, _: usize) -> Option<Self> {
-        panic!("new_remote is intentionally not implemented for ZanCanFrame since it doesn't make sense")
+    //Classic RTR frames are only built for interop, the protocol itself asks for data with RequestData frames
+    fn new_remote(id: impl Into<Id>, dlc: usize) -> Option<Self> {
+        if dlc > 8 {
+            return None;
+        }
+        let frame = Self::from_id(id.into(), &[]).ok()?;
+        Some(Self { remote_dlc: Some(dlc as u8), ..frame })
     }
 
     fn is_extended(&self) -> bool {
-        false
+        matches!(self.id, Id::Extended(_))
     }
 
     fn is_remote_frame(&self) -> bool {
-        false
+        self.remote_dlc.is_some()
     }
 
     fn id(&self) -> Id {
@@ -45,7 +272,10 @@ impl Frame for ZanCanFrame {
     }
 
     fn dlc(&self) -> usize {
-        self.data_len
+        match self.remote_dlc {
+            Some(dlc) => dlc as usize,
+            None => self.data_len
+        }
     }
 
     fn data(&self) -> &[u8] {
@@ -54,121 +284,686 @@ impl Frame for ZanCanFrame {
 }
 
 
-impl ZanCanFrame {
+//Everything that reads a frame works for any buffer size
+impl<const N: usize> ZanCanFrame<N> {
+    //Checked constructor behind from_raw and Frame::new, data has to fit in N bytes
+    fn from_id(id: Id, data: &[u8]) -> Result<Self, ZanCanError> {
+        let f_type = ZanCanFrameType::try_from(id)?;
+        if data.len() > N {
+            return Err(ZanCanError::OversizedData);
+        }
+        if !Self::is_valid_data_len(data.len()) {
+            return Err(ZanCanError::InvalidDataLength);
+        }
 
-    pub fn from_frame<F: Frame>(f: F) -> Self {
-        let f_type = ZanCanFrameType::from(f.id());
-        let mut data = [0u8; 8];
+        let mut buff = [0u8; N];
         let mut i: usize = 0;
+        while i < data.len() {
+            buff[i] = data[i];
+            i += 1;
+        }
+
+        Ok(Self { id, data_len: data.len(), data: buff, f_type, remote_dlc: None })
+    }
+
+    //CAN-FD only has dlc codes for these lengths, classic lengths of 0 to 8 are always fine
+    pub const fn is_valid_data_len(len: usize) -> bool {
+        matches!(len, 0..=8 | 12 | 16 | 20 | 24 | 32 | 48 | 64)
+    }
+
+    //Number of bytes in use. Every constructor caps data_len at N so this is always a safe slice length into the buffer
+    pub fn significant_len(&self) -> usize {
+        self.data_len.min(N)
+    }
+
+    //Significant bytes whatever the frame type, same as Frame::data without needing the trait in scope
+    pub fn raw_payload(&self) -> &[u8] {
+        &self.data[..self.data_len]
+    }
 
-        while i < f.data().len() {
-            data[i] = f.data()[i];
+    //Significant bytes as space separated lowercase ASCII hex, the same as Display shows them. Returns how many bytes were written
+    pub fn write_hex(&self, buf: &mut [u8]) -> Result<usize, ZanCanError> {
+        const HEX: &[u8; 16] = b"0123456789abcdef";
+        let needed = if self.data_len == 0 { 0 } else { self.data_len * 3 - 1 };
+        if buf.len() < needed {
+            return Err(ZanCanError::BufferTooSmall);
+        }
+
+        let mut i: usize = 0;
+        while i < self.data_len {
+            let pos = i * 3;
+            if i > 0 {
+                buf[pos - 1] = b' ';
+            }
+            buf[pos] = HEX[(self.data[i] >> 4) as usize];
+            buf[pos + 1] = HEX[(self.data[i] & 0x0F) as usize];
             i += 1;
         }
-        
-        Self { id: f.id(), data_len: f.dlc(), data, f_type }
+        Ok(needed)
+    }
+
+    //Converts into a driver's own frame type for transmitting, None if that type can't hold this frame
+    pub fn to_frame<F: Frame>(&self) -> Option<F> {
+        match self.remote_dlc {
+            Some(dlc) => F::new_remote(self.id, dlc as usize),
+            None => F::new(self.id, self.data())
+        }
+    }
+
+    //Bare id value for logging and hashing. u32 so extended ids fit too, a standard id is always 0x7FF or below
+    pub fn raw_id(&self) -> u32 {
+        match self.id {
+            Id::Standard(sid) => sid.as_raw() as u32,
+            Id::Extended(eid) => eid.as_raw()
+        }
+    }
+
+    //Raw id and significant data together, everything a transmit site or logger needs in one call.
+    //Same id rules as raw_id, remote frames give an empty slice
+    pub fn wire_view(&self) -> (u32, &[u8]) {
+        (self.raw_id(), self.raw_payload())
+    }
+
+    //Pre-flight check before decoding: the stored type agrees with the id, the length fits and it is long enough
+    //for the type. Fixed length types need expected_len bytes, data types need an identifier byte plus anything they carry after it
+    pub fn validate(&self) -> Result<(), ZanCanError> {
+        self.resolved_type()?;
+        if self.data_len > N {
+            return Err(ZanCanError::OversizedData);
+        }
+        let min_len = match self.f_type {
+            ZanCanFrameType::RequestData if self.remote_dlc.is_some() => 0,
+            ZanCanFrameType::RequestData => 1,
+            ZanCanFrameType::DataNak | ZanCanFrameType::SentData | ZanCanFrameType::SetData => 2,
+            t => t.expected_len().unwrap_or(0)
+        };
+        if self.data_len < min_len {
+            return Err(ZanCanError::TruncatedData);
+        }
+        Ok(())
+    }
+
+    //Frame type only if the stored type and the type in the id agree. Decoders go by the stored type so check this
+    //first for frames from from_parts or anywhere else the two could have drifted apart
+    pub fn resolved_type(&self) -> Result<ZanCanFrameType, ZanCanError> {
+        let id_type = ZanCanFrameType::try_from(self.id)?;
+        if id_type == self.f_type {
+            Ok(id_type)
+        } else {
+            Err(ZanCanError::TypeIdMismatch { id_type, stored: self.f_type })
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_ok()
+    }
+
+    fn shorter_than_expected(&self) -> bool {
+        self.data_len < self.f_type.expected_len().unwrap_or(0)
     }
 
     pub fn frame_type(&self) -> ZanCanFrameType {
         self.f_type
     }
 
-    pub fn new_emergency(addr: ZanCanAddress, status: EmegencyStatus, reason: EmergencyReason) -> ZanCanFrame {
-        let reason_u16 = u16::from(reason);
-        let mut data = [0u8; 8];
-        //First bit of reason should always be 0 due to checking in creation of reason. Therefor logic or the status into first bit with the reason
-        data[0] = u8::from(status) | ( reason_u16 >> 8) as u8;
-        //Rest of reason goes into the second byte
-        data[1] = reason_u16 as u8;
+    //Same as the frame type priority, the address only breaks ties between frames of the same type
+    pub fn priority(&self) -> u8 {
+        self.f_type.priority()
+    }
+
+    pub fn is_data_frame(&self) -> bool {
+        self.f_type.is_data()
+    }
 
-        ZanCanFrame{id: id_from_type_and_address(ZanCanFrameType::Emergency, addr), data_len: 2, data, f_type: ZanCanFrameType::Emergency}
+    pub fn is_fault_frame(&self) -> bool {
+        self.f_type.is_fault()
     }
 
-    pub fn decode_emergency(&self) -> Result<(EmegencyStatus, EmergencyReason), &str> {
+    fn address_bit_length(&self) -> usize {
+        match self.id {
+            Id::Standard(_) => address::ADDRESS_BIT_LENGTH,
+            Id::Extended(_) => address::EXTENDED_ADDRESS_BIT_LENGTH
+        }
+    }
+
+    //Frame type code from the bits of the id above the address
+    pub fn type_code(&self) -> u8 {
+        (self.raw_id() >> self.address_bit_length()) as u8
+    }
+
+    //Address lives in the lower bits of the id under the frame type.
+    //For extended frames this is only the low byte of the address, see extended_address
+    pub fn address_bits(&self) -> u8 {
+        (self.raw_id() & address::MAX_ADDRESS as u32) as u8
+    }
+
+    pub fn address(&self) -> ZanCanAddress {
+        ZanCanAddress::from(self.address_bits())
+    }
+
+    //Full address for either id width. Standard frames always fit in the low byte
+    pub fn extended_address(&self) -> ExtendedAddress {
+        let mask = (1u32 << self.address_bit_length()) - 1;
+        ExtendedAddress::new(self.raw_id() & mask).expect("masked address always fits in an ExtendedAddress")
+    }
+
+    //Same frame sent from a different address, payload is copied through untouched. Keeps the id width of the frame
+    pub fn with_address(&self, addr: ZanCanAddress) -> Self {
+        let id = match self.id {
+            Id::Standard(_) => id_from_type_and_address(self.f_type, addr).expect("every frame type fits in a standard id with any address"),
+            Id::Extended(_) => id_from_type_and_extended_address(self.f_type, ExtendedAddress::from(addr)).expect("every frame type fits in an extended id with any address")
+        };
+        Self { id, ..*self }
+    }
+
+    //Moves the frame onto an extended id. This is how extended frames are built, e.g.
+    //ZanCanFrame::new_heartbeat(addr, uptime).with_extended_address(wide_addr)
+    pub fn with_extended_address(&self, addr: ExtendedAddress) -> Self {
+        let id = id_from_type_and_extended_address(self.f_type, addr).expect("every frame type fits in an extended id with any address");
+        Self { id, ..*self }
+    }
+
+    //Same sender and same decoded content, however the frames were built. Frames that don't decode
+    //(Time, or malformed ones) fall back to comparing their significant bytes
+    pub fn semantically_eq(&self, other: &Self) -> bool {
+        if self.f_type != other.f_type || self.extended_address() != other.extended_address() {
+            return false;
+        }
+        match (self.decode(), other.decode()) {
+            (Ok(mine), Ok(theirs)) => mine == theirs,
+            _ => self == other
+        }
+    }
+
+    //Decodes the payload according to the frame type so a receiver only has to match once
+    pub fn decode(&self) -> Result<ZanCanMessage, ZanCanError> {
+        match self.f_type {
+            ZanCanFrameType::Emergency => {
+                let (status, reason) = self.decode_emergency()?;
+                Ok(ZanCanMessage::Emergency(status, reason))
+            },
+            ZanCanFrameType::Error => match self.decode_error_status()? {
+                (code, false) => Ok(ZanCanMessage::Error(code)),
+                (code, true) => Ok(ZanCanMessage::ErrorCleared(code))
+            },
+            ZanCanFrameType::SentData => Ok(ZanCanMessage::SentData(self.decode_sent_data()?)),
+            ZanCanFrameType::RequestData => Ok(ZanCanMessage::RequestData(self.decode_request_data()?)),
+            ZanCanFrameType::DataNak => {
+                let (data_id, reason) = self.decode_data_nak()?;
+                Ok(ZanCanMessage::DataNak(data_id, reason))
+            },
+            ZanCanFrameType::SetData => Ok(ZanCanMessage::SetData(self.decode_set_data()?)),
+            ZanCanFrameType::Heartbeat => Ok(ZanCanMessage::Heartbeat(self.decode_heartbeat()?)),
+            ZanCanFrameType::Time => Err(ZanCanError::UnsupportedFrameType(self.f_type))
+        }
+    }
+
+    //decode plus who sent it, so a receive loop can route on one match. Extended frames whose address doesn't fit
+    //in a ZanCanAddress give ExtendedIdNotSupported, use extended_address with decode for those
+    pub fn decode_with_address(&self) -> Result<(ZanCanAddress, ZanCanMessage), ZanCanError> {
+        let addr = ZanCanAddress::try_from(self.id)?;
+        Ok((addr, self.decode()?))
+    }
+
+    pub fn decode_emergency(&self) -> Result<(EmergencyStatus, EmergencyReason), ZanCanError> {
         if self.f_type != ZanCanFrameType::Emergency {
-            Err("Cannot decode emergency frame if not of emergency type")
+            Err(ZanCanError::WrongFrameType { expected: ZanCanFrameType::Emergency, found: self.f_type })
+        } else if self.shorter_than_expected() {
+            Err(ZanCanError::TruncatedData)
         } else {
-            let status = EmegencyStatus::try_from(self.data[0] & 0x80)?;
-            let mut reason_u16: u16 = (self.data[0] & 0x7F) as u16;
-            reason_u16 = reason_u16 << 8;
+            let status = EmergencyStatus::try_from(self.data[0] & emergency::STATUS_BIT)?;
+            let mut reason_u16: u16 = (self.data[0] & !emergency::STATUS_BIT) as u16;
+            reason_u16 <<= 8;
             reason_u16 |= self.data[1] as u16;
             let reason = EmergencyReason::try_from(reason_u16)?;
             Ok((status, reason))
         }
     }
 
-    pub fn new_error(addr: ZanCanAddress, code: error::ErrorCode) -> ZanCanFrame {
-        let mut data = [0u8; 8];
-        let error_code_u16 = code as u16;
-        data[0] = (error_code_u16 >> 8) as u8;
-        data[1] = error_code_u16 as u8;
-        ZanCanFrame{id: id_from_type_and_address(ZanCanFrameType::Error, addr), f_type: ZanCanFrameType::Error, data_len: 2, data}
+    //Code and whether it has cleared. decode_error gives the code either way.
+    //A third byte other than ERROR_RAISED or ERROR_CLEARED gives InvalidErrorStatus rather than reading as raised
+    pub fn decode_error_status(&self) -> Result<(error::ErrorCode, bool), ZanCanError> {
+        let code = self.decode_error()?;
+        if self.data_len <= 2 {
+            return Ok((code, false));
+        }
+        match self.data[2] {
+            error::ERROR_RAISED => Ok((code, false)),
+            error::ERROR_CLEARED => Ok((code, true)),
+            _ => Err(ZanCanError::InvalidErrorStatus)
+        }
     }
 
-    pub fn decode_error(&self) -> Result<error::ErrorCode, &str> {
+    pub fn decode_error(&self) -> Result<error::ErrorCode, ZanCanError> {
         if self.f_type != ZanCanFrameType::Error {
-            Err("Cannot decode error frame if not of error type")
+            Err(ZanCanError::WrongFrameType { expected: ZanCanFrameType::Error, found: self.f_type })
+        } else if self.shorter_than_expected() {
+            Err(ZanCanError::TruncatedData)
         } else {
             let mut error_code_u16: u16 = self.data[0] as u16;
-            error_code_u16 = error_code_u16 << 8;
-            error_code_u16 = error_code_u16 | (self.data[1] as u16);
+            error_code_u16 <<= 8;
+            error_code_u16 |= self.data[1] as u16;
 
             Ok(error::ErrorCode::from(error_code_u16))
         }
     }
 
-    pub fn new_sent_data(addr: ZanCanAddress, message: DataMessage) -> ZanCanFrame {
-        let mut data = [0u8; 8];
-        message.write(&mut data).expect("error occured writing data message to buffer");
-        ZanCanFrame{id: id_from_type_and_address(ZanCanFrameType::SentData, addr), f_type: ZanCanFrameType::SentData, data, data_len: message.len()}
-    }
-
-    pub fn decode_sent_data(&self) -> Result<DataMessage, &'static str> {
+    pub fn decode_sent_data(&self) -> Result<DataMessage, ZanCanError> {
         if self.f_type != ZanCanFrameType::SentData {
-            Err("Cannot decode sent data frame if not of sent data type")
+            Err(ZanCanError::WrongFrameType { expected: ZanCanFrameType::SentData, found: self.f_type })
         } else {
-            let d_m = DataMessage::try_from(&self.data[..])?;
+            let d_m = DataMessage::try_from(&self.data[..self.data_len])?;
             Ok(d_m)
         }
     }
 
-    pub fn new_request_data(addr: ZanCanAddress, data_id: DataIdentifier) -> ZanCanFrame {
-        let mut data = [0u8; 8];
-        data_id.write(&mut data).expect("error occured while writing DataIdentifier to buffer");
-
-        ZanCanFrame{id: id_from_type_and_address(ZanCanFrameType::RequestData, addr), f_type: ZanCanFrameType::RequestData, data, data_len: data_id.len()}
+    pub fn decode_request_data(&self) -> Result<DataIdentifier, ZanCanError> {
+        Ok(self.decode_request_data_with_args()?.0)
     }
 
-    pub fn decode_request_data(&self) -> Result<DataIdentifier, &'static str> {
+    //Identifier and argument bytes, the arguments are empty for a plain request. RTR requests ask for DataIdentifier::REMOTE_DEFAULT
+    pub fn decode_request_data_with_args(&self) -> Result<(DataIdentifier, &[u8]), ZanCanError> {
         if self.f_type != ZanCanFrameType::RequestData {
-            Err("Cannot decode request data frame if not of request data type")
+            Err(ZanCanError::WrongFrameType { expected: ZanCanFrameType::RequestData, found: self.f_type })
+        } else if self.remote_dlc.is_some() {
+            Ok((DataIdentifier::REMOTE_DEFAULT, &[]))
+        } else if self.data_len == 0 {
+            Err(ZanCanError::TruncatedData)
         } else {
-            let d_id = DataIdentifier::try_from(&self.data[0..self.data_len])?;
-            Ok(d_id)
+            let args_len = (self.data[0] >> 6) as usize;
+            if args_len >= self.data_len {
+                return Err(ZanCanError::TruncatedData);
+            }
+            let id_len = self.data_len - args_len;
+            let d_id = DataIdentifier::try_from(&self.data[0..id_len])?;
+            Ok((d_id, &self.data[id_len..self.data_len]))
         }
     }
 
-    pub fn new_set_data(addr: ZanCanAddress, message: DataMessage) -> ZanCanFrame {
-        let mut data = [0u8; 8];
-        message.write(&mut data).expect("error occured writing data message to buffer");
-        ZanCanFrame{id: id_from_type_and_address(ZanCanFrameType::SetData, addr), f_type: ZanCanFrameType::SetData, data, data_len: message.len()}
+    //RequestData frames and RTR frames both ask the node to send something
+    pub fn is_request(&self) -> bool {
+        self.f_type == ZanCanFrameType::RequestData || self.remote_dlc.is_some()
+    }
+
+    pub fn decode_data_nak(&self) -> Result<(DataIdentifier, NakReason), ZanCanError> {
+        if self.f_type != ZanCanFrameType::DataNak {
+            Err(ZanCanError::WrongFrameType { expected: ZanCanFrameType::DataNak, found: self.f_type })
+        } else if self.data_len < 2 {
+            Err(ZanCanError::TruncatedData)
+        } else {
+            let d_id = DataIdentifier::try_from(&self.data[0..self.data_len - 1])?;
+            Ok((d_id, NakReason::from(self.data[self.data_len - 1])))
+        }
     }
 
-    pub fn decode_set_data(&self) -> Result<DataMessage, &'static str> {
+    pub fn decode_set_data(&self) -> Result<DataMessage, ZanCanError> {
         if self.f_type != ZanCanFrameType::SetData {
-            Err("Cannot decode set data frame if not of set data type")
+            Err(ZanCanError::WrongFrameType { expected: ZanCanFrameType::SetData, found: self.f_type })
         } else {
-            let d_m = DataMessage::try_from(&self.data[..])?;
+            let d_m = DataMessage::try_from(&self.data[..self.data_len])?;
             Ok(d_m)
         }
     }
 
+    //Identifier and payload of a SetData frame without building a DataMessage, the payload borrows from the frame
+    pub fn decode_set_target(&self) -> Result<(DataIdentifier, &[u8]), ZanCanError> {
+        if self.f_type != ZanCanFrameType::SetData {
+            return Err(ZanCanError::WrongFrameType { expected: ZanCanFrameType::SetData, found: self.f_type });
+        }
+        let id_len = self.data_identifier_len()?;
+        let d_id = DataIdentifier::try_from(&self.data[..id_len])?;
+        Ok((d_id, &self.data[id_len..self.data_len]))
+    }
+
+    pub fn decode_heartbeat(&self) -> Result<u32, ZanCanError> {
+        if self.f_type != ZanCanFrameType::Heartbeat {
+            Err(ZanCanError::WrongFrameType { expected: ZanCanFrameType::Heartbeat, found: self.f_type })
+        } else if self.shorter_than_expected() {
+            Err(ZanCanError::TruncatedData)
+        } else {
+            let mut uptime_ms: u32 = 0;
+            let mut i: usize = 0;
+            while i < 4 {
+                uptime_ms = (uptime_ms << 8) | self.data[i] as u32;
+                i += 1;
+            }
+            Ok(uptime_ms)
+        }
+    }
+
+    //Supported types mask and protocol version from a new_capability frame. Other SentData gives InvalidDataIdentifier
+    pub fn decode_capability(&self) -> Result<(u8, u8), ZanCanError> {
+        let message = self.decode_sent_data()?;
+        if message.data_identifier() != DataIdentifier::DEVICE_CAPABILITY {
+            return Err(ZanCanError::InvalidDataIdentifier);
+        }
+        match message.data() {
+            [supported_types, proto_version, ..] => Ok((*supported_types, *proto_version)),
+            _ => Err(ZanCanError::TruncatedData)
+        }
+    }
+
+    //How many bytes at the start of a data frame are the DataIdentifier
+    fn data_identifier_len(&self) -> Result<usize, ZanCanError> {
+        match self.f_type {
+            ZanCanFrameType::SentData | ZanCanFrameType::SetData => {
+                if self.data_len == 0 {
+                    return Err(ZanCanError::TruncatedData);
+                }
+                let payload_len = message_data::payload_len_from_header(self.data[0]);
+                if self.data_len <= payload_len {
+                    Err(ZanCanError::TruncatedData)
+                } else if self.data_len - payload_len > message_data::MAX_IDENTIFIER_LENGTH {
+                    Err(ZanCanError::OversizedData)
+                } else {
+                    Ok(self.data_len - payload_len)
+                }
+            },
+            ZanCanFrameType::RequestData => {
+                //Argument bytes after the identifier aren't part of it, see new_request_data_with_args
+                if self.data_len == 0 || (self.data[0] >> 6) as usize >= self.data_len {
+                    Err(ZanCanError::TruncatedData)
+                } else {
+                    Ok(self.data_len - (self.data[0] >> 6) as usize)
+                }
+            },
+            _ => Err(ZanCanError::UnsupportedFrameType(self.f_type))
+        }
+    }
+
+    //Parses only the identifier of a data frame so a receiver can skip frames it doesn't care about
+    pub fn data_identifier(&self) -> Result<DataIdentifier, ZanCanError> {
+        let id_len = self.data_identifier_len()?;
+        DataIdentifier::try_from(&self.data[..id_len])
+    }
+
+    //Payload bytes after the identifier of a data frame, empty if the frame has no payload
+    pub fn data_payload(&self) -> &[u8] {
+        match self.data_identifier_len() {
+            Ok(id_len) => &self.data[id_len..self.data_len],
+            Err(_) => &[]
+        }
+    }
+
+    //Copies the frame into a different buffer size, e.g. a classic frame into a CAN-FD sized one.
+    //None if the significant bytes don't fit in M
+    pub fn resize<const M: usize>(&self) -> Option<ZanCanFrame<M>> {
+        if self.data_len > M {
+            return None;
+        }
+        let mut data = [0u8; M];
+        data[..self.data_len].copy_from_slice(self.raw_payload());
+        Some(ZanCanFrame { id: self.id, data_len: self.data_len, data, f_type: self.f_type, remote_dlc: self.remote_dlc })
+    }
 }
 
-fn id_from_type_and_address(t: ZanCanFrameType, addr: ZanCanAddress) -> Id {
+//Constructors build classic 8 byte frames, use resize or Frame::new for other sizes
+impl ZanCanFrame {
+    //Same as Frame::new and Frame::new_remote. These pin ZanCanFrame::new to the classic 8 byte frame so it doesn't need a
+    //type annotation, call the trait methods on ZanCanFrame<N> for other sizes
+    pub fn new(id: impl Into<Id>, data: &[u8]) -> Option<ZanCanFrame> {
+        <ZanCanFrame as Frame>::new(id, data)
+    }
+
+    pub fn new_remote(id: impl Into<Id>, dlc: usize) -> Option<ZanCanFrame> {
+        <ZanCanFrame as Frame>::new_remote(id, dlc)
+    }
+
+    //Entry point for untrusted bus traffic. Checks the id fits in 11 bits, holds a known frame type and that the data fits
+    pub fn from_raw(id: u16, data: &[u8]) -> Result<ZanCanFrame, ZanCanError> {
+        let id = Id::Standard(StandardId::new(id).ok_or(ZanCanError::InvalidId)?);
+        ZanCanFrame::from_id(id, data)
+    }
+
+    //Same as from_raw for a 29 bit extended id
+    pub fn from_raw_extended(id: u32, data: &[u8]) -> Result<ZanCanFrame, ZanCanError> {
+        let id = Id::Extended(ExtendedId::new(id).ok_or(ZanCanError::InvalidId)?);
+        ZanCanFrame::from_id(id, data)
+    }
+
+    //No checks at all, for building the malformed frames negative tests need. The type doesn't have to match the id
+    //and len can claim more bytes than data holds meaningfully. len is still capped at 8 since data() slices the buffer
+    #[cfg(any(test, feature = "test-util"))]
+    pub fn raw_unchecked(id: Id, data: [u8; 8], len: usize, f_type: ZanCanFrameType) -> ZanCanFrame {
+        ZanCanFrame { id, data_len: len.min(8), data, f_type, remote_dlc: None }
+    }
+
+    #[deprecated(note = "panics on an unknown frame type, use from_frame_truncating or try_from_frame")]
+    pub fn from_frame<F: Frame>(f: F) -> Self {
+        ZanCanFrame::from_frame_truncating(f).expect("frame id does not hold a known ZanCanFrameType")
+    }
+
+    //Keeps what fits when the source frame has more than 8 bytes. Only errors if the id doesn't hold a known frame type
+    pub fn from_frame_truncating<F: Frame>(f: F) -> Result<Self, ZanCanError> {
+        let f_type = ZanCanFrameType::try_from(f.id())?;
+        if f.is_remote_frame() {
+            //RTR frames carry no data, only the length they are asking for
+            return Ok(Self { id: f.id(), data_len: 0, data: [0u8; 8], f_type, remote_dlc: Some(f.dlc().min(8) as u8) });
+        }
+
+        //Source frames can claim more than 8 bytes (CAN-FD or a bad driver), and a dlc that disagrees with their data.
+        //Only the bytes both agree on and that fit are kept, so data_len is always the number of bytes copied
+        let mut data = [0u8; 8];
+        let n = f.data().len().min(f.dlc()).min(data.len());
+        data[..n].copy_from_slice(&f.data()[..n]);
+        Ok(Self { id: f.id(), data_len: n, data, f_type, remote_dlc: None })
+    }
+
+    //Strict version of from_frame_truncating, a source frame with more than 8 bytes or a dlc past 8 gives OversizedData
+    pub fn try_from_frame<F: Frame>(f: F) -> Result<Self, ZanCanError> {
+        if f.data().len() > 8 || f.dlc() > 8 {
+            return Err(ZanCanError::OversizedData);
+        }
+        ZanCanFrame::from_frame_truncating(f)
+    }
+
+    //Pulls the frame apart for queues that store the pieces separately. The last part is the requested dlc of an RTR frame
+    pub fn into_parts(self) -> (Id, [u8; 8], usize, ZanCanFrameType, Option<u8>) {
+        (self.id, self.data, self.data_len, self.f_type, self.remote_dlc)
+    }
+
+    //Puts a frame back together from into_parts without re-deriving the type from the id.
+    //Debug builds check that the type still matches the id. A data_len or remote dlc past the buffer is capped at 8,
+    //and RTR frames never carry data
+    pub fn from_parts(id: Id, data: [u8; 8], data_len: usize, f_type: ZanCanFrameType, remote_dlc: Option<u8>) -> ZanCanFrame {
+        debug_assert!(ZanCanFrameType::try_from(id) == Ok(f_type), "frame type does not match the type in the id");
+        match remote_dlc {
+            Some(dlc) => ZanCanFrame { id, data_len: 0, data: [0u8; 8], f_type, remote_dlc: Some(dlc.min(8)) },
+            None => ZanCanFrame { id, data_len: data_len.min(8), data, f_type, remote_dlc: None }
+        }
+    }
+
+    //Fixed 10 byte layout for flash or a non CAN link: a big endian u16 with the RTR flag in the top bit and the dlc in the
+    //4 bits above the 11 bit id, then 8 data bytes with unused ones zeroed. RTR frames keep their requested dlc and no data.
+    //Only standard ids fit so extended frames give ExtendedIdNotSupported
+    pub fn to_bytes(&self) -> Result<[u8; 10], ZanCanError> {
+        if self.is_extended() {
+            return Err(ZanCanError::ExtendedIdNotSupported);
+        }
+        let (raw_id, data) = self.wire_view();
+        let mut bytes = [0u8; 10];
+        let header = match self.remote_dlc {
+            Some(dlc) => BYTES_RTR_FLAG | ((dlc as u16) << BYTES_DLC_SHIFT) | raw_id as u16,
+            None => ((data.len() as u16) << BYTES_DLC_SHIFT) | raw_id as u16
+        };
+        bytes[..2].copy_from_slice(&header.to_be_bytes());
+        bytes[2..2 + data.len()].copy_from_slice(data);
+        Ok(bytes)
+    }
+
+    //Reverse of to_bytes, checked the same as from_raw. A dlc past 8 gives OversizedData
+    pub fn from_bytes(bytes: &[u8; 10]) -> Result<ZanCanFrame, ZanCanError> {
+        let header = u16::from_be_bytes([bytes[0], bytes[1]]);
+        let dlc = ((header & !BYTES_RTR_FLAG) >> BYTES_DLC_SHIFT) as usize;
+        if dlc > 8 {
+            return Err(ZanCanError::OversizedData);
+        }
+        if header & BYTES_RTR_FLAG != 0 {
+            let frame = ZanCanFrame::from_raw(header & BYTES_ID_MASK, &[])?;
+            return Ok(ZanCanFrame { remote_dlc: Some(dlc as u8), ..frame });
+        }
+        ZanCanFrame::from_raw(header & BYTES_ID_MASK, &bytes[2..2 + dlc])
+    }
+
+    //Parses one to_bytes frame off the front of a byte stream and gives back the rest of the buffer.
+    //Fewer than 10 bytes gives NeedMoreData so a streaming reader knows to wait rather than drop the bytes
+    pub fn parse_prefix(buf: &[u8]) -> Result<(ZanCanFrame, &[u8]), ZanCanError> {
+        if buf.len() < 10 {
+            return Err(ZanCanError::NeedMoreData);
+        }
+        let (head, tail) = buf.split_at(10);
+        let bytes: &[u8; 10] = head.try_into().expect("split_at gives exactly 10 bytes");
+        Ok((ZanCanFrame::from_bytes(bytes)?, tail))
+    }
+
+    //Decodes once and keeps the result next to the frame, see InspectedFrame
+    pub fn inspect(&self) -> message::InspectedFrame {
+        message::InspectedFrame::new(*self)
+    }
+
+    //Reason goes out big endian in the first 2 bytes with the status in the top bit, see emergency::STATUS_BIT
+    pub fn new_emergency(addr: ZanCanAddress, status: EmergencyStatus, reason: EmergencyReason) -> ZanCanFrame {
+        let reason_u16 = u16::from(reason);
+        let mut data = [0u8; 8];
+        //First bit of reason should always be 0 due to checking in creation of reason. Therefor logic or the status into first bit with the reason
+        data[0] = u8::from(status) | ( reason_u16 >> 8) as u8;
+        //Rest of reason goes into the second byte
+        data[1] = reason_u16 as u8;
+
+        ZanCanFrame{id: id_from_type_and_address(ZanCanFrameType::Emergency, addr).expect("every frame type fits in a standard id with any address"), data_len: 2, data, f_type: ZanCanFrameType::Emergency, remote_dlc: None}
+    }
+
+    //Code goes out big endian in the first 2 bytes
+    pub fn new_error(addr: ZanCanAddress, code: error::ErrorCode) -> ZanCanFrame {
+        let mut data = [0u8; 8];
+        let error_code_u16 = u16::from(code);
+        data[0] = (error_code_u16 >> 8) as u8;
+        data[1] = error_code_u16 as u8;
+        ZanCanFrame{id: id_from_type_and_address(ZanCanFrameType::Error, addr).expect("every frame type fits in a standard id with any address"), f_type: ZanCanFrameType::Error, data_len: 2, data, remote_dlc: None}
+    }
+
+    //Same as new_error with a third byte saying whether the code is being raised or has cleared.
+    //Frames from new_error have no third byte and read as raised
+    pub fn new_error_with_status(addr: ZanCanAddress, code: error::ErrorCode, cleared: bool) -> ZanCanFrame {
+        let mut frame = ZanCanFrame::new_error(addr, code);
+        frame.data[2] = if cleared { error::ERROR_CLEARED } else { error::ERROR_RAISED };
+        frame.data_len = 3;
+        frame
+    }
+
+    pub fn new_error_cleared(addr: ZanCanAddress, code: error::ErrorCode) -> ZanCanFrame {
+        ZanCanFrame::new_error_with_status(addr, code, true)
+    }
+
+    pub fn try_new_sent_data(addr: ZanCanAddress, message: DataMessage) -> Result<ZanCanFrame, ZanCanError> {
+        let mut data = [0u8; 8];
+        let data_len = message.write(&mut data)?;
+        Ok(ZanCanFrame{id: id_from_type_and_address(ZanCanFrameType::SentData, addr)?, f_type: ZanCanFrameType::SentData, data, data_len, remote_dlc: None})
+    }
+
+    //Only safe for messages built through DataMessage::new or try_from which guarantee they fit in a frame
+    pub fn new_sent_data(addr: ZanCanAddress, message: DataMessage) -> ZanCanFrame {
+        ZanCanFrame::try_new_sent_data(addr, message).expect("error occured writing data message to buffer")
+    }
+
+    pub fn new_request_data(addr: ZanCanAddress, data_id: DataIdentifier) -> ZanCanFrame {
+        let mut data = [0u8; 8];
+        data_id.write(&mut data).expect("error occured while writing DataIdentifier to buffer");
+
+        ZanCanFrame{id: id_from_type_and_address(ZanCanFrameType::RequestData, addr).expect("every frame type fits in a standard id with any address"), f_type: ZanCanFrameType::RequestData, data, data_len: data_id.len(), remote_dlc: None}
+    }
+
+    //One request frame per identifier. Identifiers aren't packed together since a request frame only carries one
+    pub fn new_request_data_batch(addr: ZanCanAddress, ids: &[DataIdentifier]) -> impl Iterator<Item = ZanCanFrame> + '_ {
+        ids.iter().map(move |data_id| ZanCanFrame::new_request_data(addr, *data_id))
+    }
+
+    //Request with up to 3 argument bytes after the identifier, like a sample number or channel.
+    //The top 2 bits of the first identifier byte hold the argument count, 0 for plain requests so those are unchanged
+    pub fn new_request_data_with_args(addr: ZanCanAddress, data_id: DataIdentifier, args: &[u8]) -> Result<ZanCanFrame, ZanCanError> {
+        if args.len() > MAX_REQUEST_ARGS {
+            return Err(ZanCanError::PayloadTooLarge);
+        }
+        let mut frame = ZanCanFrame::new_request_data(addr, data_id);
+        let id_len = frame.data_len;
+        frame.data[0] |= (args.len() as u8) << 6;
+        frame.data[id_len..id_len + args.len()].copy_from_slice(args);
+        frame.data_len = id_len + args.len();
+        Ok(frame)
+    }
+
+    //Classic RTR request for interop with generic CAN tools. It carries no data identifier so it decodes as a request for
+    //DataIdentifier::REMOTE_DEFAULT and Node answers it like one. new_request_data is still the normal way to ask for data
+    pub fn new_remote_request(addr: ZanCanAddress, dlc: usize) -> Result<ZanCanFrame, ZanCanError> {
+        let id = id_from_type_and_address(ZanCanFrameType::RequestData, addr)?;
+        ZanCanFrame::new_remote(id, dlc).ok_or(ZanCanError::OversizedData)
+    }
+
+    //Answer to a RequestData the node can't serve. Identifier bytes first then one reason byte
+    pub fn new_data_nak(addr: ZanCanAddress, data_id: DataIdentifier, reason: NakReason) -> ZanCanFrame {
+        let mut data = [0u8; 8];
+        let id_len = data_id.write(&mut data).expect("error occured while writing DataIdentifier to buffer");
+        data[id_len] = u8::from(reason);
+
+        ZanCanFrame{id: id_from_type_and_address(ZanCanFrameType::DataNak, addr).expect("every frame type fits in a standard id with any address"), f_type: ZanCanFrameType::DataNak, data, data_len: id_len + 1, remote_dlc: None}
+    }
+
+    pub fn try_new_set_data(addr: ZanCanAddress, message: DataMessage) -> Result<ZanCanFrame, ZanCanError> {
+        let mut data = [0u8; 8];
+        let data_len = message.write(&mut data)?;
+        Ok(ZanCanFrame{id: id_from_type_and_address(ZanCanFrameType::SetData, addr)?, f_type: ZanCanFrameType::SetData, data, data_len, remote_dlc: None})
+    }
+
+    //Only safe for messages built through DataMessage::new or try_from which guarantee they fit in a frame
+    pub fn new_set_data(addr: ZanCanAddress, message: DataMessage) -> ZanCanFrame {
+        ZanCanFrame::try_new_set_data(addr, message).expect("error occured writing data message to buffer")
+    }
+
+    //What a node supports, as a SentData frame for DataIdentifier::DEVICE_CAPABILITY. Every type code is taken so this
+    //can't be a frame type of its own, and old nodes already know how to ignore data they don't recognise.
+    //supported_types is a mask of ZanCanFrameType::mask_bit values
+    pub fn new_capability(addr: ZanCanAddress, supported_types: u8, proto_version: u8) -> ZanCanFrame {
+        let message = DataMessage::new(DataIdentifier::DEVICE_CAPABILITY, &[supported_types, proto_version]).expect("2 byte payload always fits in a DataMessage");
+        ZanCanFrame::new_sent_data(addr, message)
+    }
+
+    //Uptime or a sequence counter, whichever the node prefers, sent big endian in the first 4 bytes
+    pub fn new_heartbeat(addr: ZanCanAddress, uptime_ms: u32) -> ZanCanFrame {
+        let mut data = [0u8; 8];
+        data[0] = (uptime_ms >> 24) as u8;
+        data[1] = (uptime_ms >> 16) as u8;
+        data[2] = (uptime_ms >> 8) as u8;
+        data[3] = uptime_ms as u8;
+        ZanCanFrame{id: id_from_type_and_address(ZanCanFrameType::Heartbeat, addr).expect("every frame type fits in a standard id with any address"), f_type: ZanCanFrameType::Heartbeat, data_len: 4, data, remote_dlc: None}
+    }
+
+}
+
+//Builds the standard id for a frame type sent from an address, errors if the combination doesn't fit in 11 bits
+pub fn id_from_type_and_address(t: ZanCanFrameType, addr: ZanCanAddress) -> Result<Id, ZanCanError> {
     let mut id_u16: u16 = 0x0000;
     id_u16 |= u8::from(t) as u16;
-    id_u16 = id_u16 << address::ADDRESS_BIT_LENGTH;
+    id_u16 <<= address::ADDRESS_BIT_LENGTH;
     id_u16 |= u8::from(addr) as u16;
-    Id::Standard(StandardId::new(id_u16).expect("something went horribly wrong creating id from type and address"))
+    match StandardId::new(id_u16) {
+        Some(sid) => Ok(Id::Standard(sid)),
+        None => Err(ZanCanError::InvalidId)
+    }
+}
+
+//to_bytes header, the dlc sits above the 11 bit standard id and the RTR flag above that
+const BYTES_DLC_SHIFT: u16 = 11;
+const BYTES_ID_MASK: u16 = (1 << BYTES_DLC_SHIFT) - 1;
+const BYTES_RTR_FLAG: u16 = 0x8000;
+
+//Argument count has to fit in the 2 spare bits of the first identifier byte
+const MAX_REQUEST_ARGS: usize = 3;
+
+//Extended id mode, the type code sits above a 26 bit address
+pub fn id_from_type_and_extended_address(t: ZanCanFrameType, addr: ExtendedAddress) -> Result<Id, ZanCanError> {
+    let raw = ((u8::from(t) as u32) << address::EXTENDED_ADDRESS_BIT_LENGTH) | u32::from(addr);
+    match ExtendedId::new(raw) {
+        Some(eid) => Ok(Id::Extended(eid)),
+        None => Err(ZanCanError::InvalidId)
+    }
 }
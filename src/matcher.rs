@@ -0,0 +1,55 @@
+
+//Declarative receive filter, e.g. emergencies from one node or any SetData.
+//Leaving a field unset matches anything for it
+
+use super::address::{ExtendedAddress, ZanCanAddress, ADDRESS_BIT_LENGTH, MAX_ADDRESS};
+use super::zan_can_type::ZanCanFrameType;
+use super::ZanCanFrame;
+
+//Type code bits that sit above the address in a standard id
+const TYPE_MASK: u16 = 0x07;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FrameMatcher {
+    frame_type: Option<ZanCanFrameType>,
+    address: Option<ZanCanAddress>
+}
+
+impl FrameMatcher {
+    //Matches every frame until narrowed down
+    pub fn new() -> FrameMatcher {
+        FrameMatcher { frame_type: None, address: None }
+    }
+
+    pub fn frame_type(self, frame_type: ZanCanFrameType) -> FrameMatcher {
+        FrameMatcher { frame_type: Some(frame_type), ..self }
+    }
+
+    pub fn address(self, address: ZanCanAddress) -> FrameMatcher {
+        FrameMatcher { address: Some(address), ..self }
+    }
+
+    //(id, mask) pair for controllers with hardware acceptance filters on standard ids. A frame passes when
+    //frame_id & mask == id & mask, so unset fields are left as 0 don't care bits in the mask
+    pub fn to_acceptance_filter(&self) -> (u16, u16) {
+        let mut id: u16 = 0;
+        let mut mask: u16 = 0;
+        if let Some(t) = self.frame_type {
+            id |= (u8::from(t) as u16) << ADDRESS_BIT_LENGTH;
+            mask |= TYPE_MASK << ADDRESS_BIT_LENGTH;
+        }
+        if let Some(a) = self.address {
+            id |= u8::from(a) as u16;
+            mask |= MAX_ADDRESS as u16;
+        }
+        (id, mask)
+    }
+
+    //Extended frames have to carry exactly this address, not just share its low byte
+    pub fn matches(&self, frame: &ZanCanFrame) -> bool {
+        let type_matches = self.frame_type.is_none_or(|t| t == frame.frame_type());
+        let address_matches = self.address.is_none_or(|a| ExtendedAddress::from(a) == frame.extended_address());
+        type_matches && address_matches
+    }
+}
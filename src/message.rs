@@ -0,0 +1,64 @@
+
+use super::emergency::{EmergencyStatus, EmergencyReason};
+use super::error::{ErrorCode, ZanCanError};
+use super::address::ZanCanAddress;
+use super::message_data::{DataIdentifier, DataMessage, NakReason};
+use super::ZanCanFrame;
+
+//Decoded payload of a ZanCanFrame, one variant per frame type that has something to decode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZanCanMessage {
+    Emergency(EmergencyStatus, EmergencyReason),
+    Error(ErrorCode),
+    //The error was reported before and has now gone away
+    ErrorCleared(ErrorCode),
+    SentData(DataMessage),
+    RequestData(DataIdentifier),
+    DataNak(DataIdentifier, NakReason),
+    SetData(DataMessage),
+    Heartbeat(u32)
+}
+
+impl ZanCanMessage {
+    //Builds the frame for this message sent from addr, the reverse of ZanCanFrame::decode
+    pub fn encode(&self, addr: ZanCanAddress) -> ZanCanFrame {
+        match *self {
+            ZanCanMessage::Emergency(status, reason) => ZanCanFrame::new_emergency(addr, status, reason),
+            ZanCanMessage::Error(code) => ZanCanFrame::new_error(addr, code),
+            ZanCanMessage::ErrorCleared(code) => ZanCanFrame::new_error_cleared(addr, code),
+            ZanCanMessage::SentData(message) => ZanCanFrame::new_sent_data(addr, message),
+            ZanCanMessage::RequestData(data_id) => ZanCanFrame::new_request_data(addr, data_id),
+            ZanCanMessage::DataNak(data_id, reason) => ZanCanFrame::new_data_nak(addr, data_id, reason),
+            ZanCanMessage::SetData(message) => ZanCanFrame::new_set_data(addr, message),
+            ZanCanMessage::Heartbeat(uptime_ms) => ZanCanFrame::new_heartbeat(addr, uptime_ms)
+        }
+    }
+}
+
+//A frame with its payload decoded once up front, so several handlers can look at the message without
+//each one parsing the bytes again. Copy and allocation free like the frame itself
+#[derive(Debug, Clone, Copy)]
+pub struct InspectedFrame {
+    frame: ZanCanFrame,
+    message: Result<ZanCanMessage, ZanCanError>
+}
+
+impl InspectedFrame {
+    pub fn new(frame: ZanCanFrame) -> InspectedFrame {
+        InspectedFrame { frame, message: frame.decode() }
+    }
+
+    pub fn frame(&self) -> &ZanCanFrame {
+        &self.frame
+    }
+
+    //Same result decode gave when this was built
+    pub fn message(&self) -> Result<ZanCanMessage, ZanCanError> {
+        self.message
+    }
+}
+
+//Decodes every frame of a stream, keeping errors per frame so one bad frame doesn't stop the rest
+pub fn decode_iter<I: Iterator<Item = ZanCanFrame>>(frames: I) -> impl Iterator<Item = Result<ZanCanMessage, ZanCanError>> {
+    frames.map(|frame| frame.decode())
+}
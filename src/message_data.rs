@@ -1,8 +1,17 @@
 
-const MAX_IDENTIFIER_LENGTH: usize = 4;
+use super::error::ZanCanError;
+
+pub(crate) const MAX_IDENTIFIER_LENGTH: usize = 4;
 const MAX_DATA_LENGTH: usize = 4;
 
-#[derive(Debug, Clone, Copy)]
+//Payload length is stored as length - 1 in the top 2 bits of the first identifier byte
+pub(crate) fn payload_len_from_header(first_byte: u8) -> usize {
+    (((first_byte & 0xC0) >> 6) + 1) as usize
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(into = "u32"))]
 pub struct DataIdentifier {
     identifier: u32
 }
@@ -14,11 +23,11 @@ impl From<DataIdentifier> for u32 {
 }
 
 impl TryFrom<u32> for DataIdentifier {
-    type Error = &'static str;
+    type Error = ZanCanError;
 
     fn try_from(value: u32) -> Result<Self, Self::Error> {
         if value >= 0x4000_0000 {
-            Err("Unable to convert u32 to DataIdentifier due to data in u32 being larger than or equal to 0x4000_0000")
+            Err(ZanCanError::InvalidDataIdentifier)
         } else {
             Ok(DataIdentifier{identifier: value})
         }
@@ -26,26 +35,25 @@ impl TryFrom<u32> for DataIdentifier {
 }
 
 impl TryFrom<&[u8]> for DataIdentifier {
-    type Error = &'static str;
+    type Error = ZanCanError;
 
     fn try_from(buff: &[u8]) -> Result<Self, Self::Error> {
-        if buff.len() == 0 {
-            Err("unable to convert &[u8] to DataIdentifier due to slice being size 0")
+        if buff.is_empty() {
+            Err(ZanCanError::TruncatedData)
         } else if buff.len() > MAX_IDENTIFIER_LENGTH {
-            Err("unable to convert &[u8] to DataIdentifier due to slize being over maximum size")
+            Err(ZanCanError::OversizedData)
         } else {
             let mut d_id = DataIdentifier{identifier: 0};
 
             let mut i: usize = 0;
 
             while i < buff.len() {
-                let cur_sequence: u8;
-                if i == 0 {
+                let cur_sequence: u8 = if i == 0 {
                     //First byte has the data length maybe in it so lets purge that
-                    cur_sequence = buff[i] & 0x3F;
+                    buff[i] & 0x3F
                 } else {
-                    cur_sequence = buff[i];
-                }
+                    buff[i]
+                };
                 d_id.identifier = (d_id.identifier << 8) | (cur_sequence as u32);
                 i += 1;
             };
@@ -56,6 +64,53 @@ impl TryFrom<&[u8]> for DataIdentifier {
 }
 
 impl DataIdentifier {
+    //Most bytes an identifier takes up on the wire. len() gives how many a specific identifier needs
+    pub const MAX_BYTE_LEN: usize = MAX_IDENTIFIER_LENGTH;
+
+    //Identifiers every device has to support, see the README for their types
+    pub const DEVICE_MANUFACTURER: DataIdentifier = DataIdentifier { identifier: 0x00 };
+    pub const DEVICE_MODEL: DataIdentifier = DataIdentifier { identifier: 0x01 };
+    pub const DEVICE_VERSION: DataIdentifier = DataIdentifier { identifier: 0x02 };
+    pub const DEVICE_SERIAL_NUMBER: DataIdentifier = DataIdentifier { identifier: 0x03 };
+    //u8 of 0x00 Ready, 0x01 Active, 0x02 Errored
+    pub const DEVICE_STATE: DataIdentifier = DataIdentifier { identifier: 0x04 };
+    //Supported frame type bitmask then protocol version, see ZanCanFrame::new_capability
+    pub const DEVICE_CAPABILITY: DataIdentifier = DataIdentifier { identifier: 0x05 };
+    //What an RTR RequestData frame asks for, it has no data to carry an identifier in
+    pub const REMOTE_DEFAULT: DataIdentifier = DataIdentifier::DEVICE_STATE;
+
+    //Every identifier this library has a name for. Devices are free to use any other value for their own data
+    pub const ALL: [DataIdentifier; 6] = [
+        DataIdentifier::DEVICE_MANUFACTURER,
+        DataIdentifier::DEVICE_MODEL,
+        DataIdentifier::DEVICE_VERSION,
+        DataIdentifier::DEVICE_SERIAL_NUMBER,
+        DataIdentifier::DEVICE_STATE,
+        DataIdentifier::DEVICE_CAPABILITY
+    ];
+
+    pub fn all() -> impl Iterator<Item = DataIdentifier> {
+        DataIdentifier::ALL.into_iter()
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        self.identifier
+    }
+
+    pub fn name(&self) -> Option<&'static str> {
+        match *self {
+            DataIdentifier::DEVICE_MANUFACTURER => Some("DeviceManufacturer"),
+            DataIdentifier::DEVICE_MODEL => Some("DeviceModel"),
+            DataIdentifier::DEVICE_VERSION => Some("DeviceVersion"),
+            DataIdentifier::DEVICE_SERIAL_NUMBER => Some("DeviceSerialNumber"),
+            DataIdentifier::DEVICE_STATE => Some("DeviceState"),
+            DataIdentifier::DEVICE_CAPABILITY => Some("DeviceCapability"),
+            _ => None
+        }
+    }
+
+    //An identifier always takes at least one byte so there is no such thing as an empty one
+    #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
         match self.identifier {
             0x00..=0x3F => 1,
@@ -65,10 +120,10 @@ impl DataIdentifier {
         }
     }
 
-    pub fn write(&self, buff: &mut [u8]) -> Result<usize, &'static str> {
+    pub fn write(&self, buff: &mut [u8]) -> Result<usize, ZanCanError> {
         let my_len = self.len();
         if buff.len() < my_len {
-            Err("Buffer is too small to write DataIdentifier to")
+            Err(ZanCanError::BufferTooSmall)
         } else {
             let mut i: usize = 0; 
             while i < my_len {
@@ -81,6 +136,102 @@ impl DataIdentifier {
     }
 }
 
+//CRC-8 with polynomial 0x07, no reflection and a zero start value
+fn crc8(bytes: &[u8]) -> u8 {
+    let mut crc: u8 = 0;
+    let mut i: usize = 0;
+    while i < bytes.len() {
+        crc ^= bytes[i];
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x80 != 0 { (crc << 1) ^ 0x07 } else { crc << 1 };
+            bit += 1;
+        }
+        i += 1;
+    }
+    crc
+}
+
+//Sub command some nodes read from the first payload byte of a SetData instead of treating it as a value.
+//0x00 isn't a command so a zeroed payload never reads as one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SetCommand {
+    Set,
+    Clear,
+    Toggle
+}
+
+impl From<SetCommand> for u8 {
+    fn from(c: SetCommand) -> u8 {
+        match c {
+            SetCommand::Set => 0x01,
+            SetCommand::Clear => 0x02,
+            SetCommand::Toggle => 0x03
+        }
+    }
+}
+
+impl TryFrom<u8> for SetCommand {
+    type Error = ZanCanError;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0x01 => Ok(SetCommand::Set),
+            0x02 => Ok(SetCommand::Clear),
+            0x03 => Ok(SetCommand::Toggle),
+            _ => Err(ZanCanError::InvalidCommand)
+        }
+    }
+}
+
+//Why a node answered a RequestData with a DataNak. Reasons this library doesn't know are kept as Unknown
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(from = "u8", into = "u8"))]
+pub enum NakReason {
+    //Node has nothing at that identifier
+    UnsupportedIdentifier,
+    //Identifier exists but can't be read right now
+    Busy,
+    //Identifier exists but the node won't give it out
+    AccessDenied,
+    Unknown(u8)
+}
+
+impl From<u8> for NakReason {
+    fn from(v: u8) -> Self {
+        match v {
+            0x01 => NakReason::UnsupportedIdentifier,
+            0x02 => NakReason::Busy,
+            0x03 => NakReason::AccessDenied,
+            _ => NakReason::Unknown(v)
+        }
+    }
+}
+
+impl From<NakReason> for u8 {
+    fn from(r: NakReason) -> Self {
+        match r {
+            NakReason::UnsupportedIdentifier => 0x01,
+            NakReason::Busy => 0x02,
+            NakReason::AccessDenied => 0x03,
+            NakReason::Unknown(v) => v
+        }
+    }
+}
+
+//Compared by value the same as ErrorCode
+impl PartialEq for NakReason {
+    fn eq(&self, other: &Self) -> bool {
+        u8::from(*self) == u8::from(*other)
+    }
+}
+
+impl Eq for NakReason {}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct DataMessage{
     message_data_len_bytes: usize,
     data_identifier: DataIdentifier,
@@ -88,15 +239,198 @@ pub struct DataMessage{
 }
 
 impl DataMessage {
-    pub fn new(m_d_len: usize, data_id: DataIdentifier, data: [u8; MAX_DATA_LENGTH]) -> Result<DataMessage, &'static str> {
-        if m_d_len > MAX_DATA_LENGTH {
-            Err("Unable to create DataMessage due to data length being larger than max")
+    //Most payload bytes a message can carry after its identifier
+    pub const MAX_PAYLOAD_LEN: usize = MAX_DATA_LENGTH;
+
+    //Payload has to be 1 to 4 bytes since its length is stored as length - 1 in the top 2 bits of the identifier.
+    //Identifier is at most 4 bytes as well so a valid message always fits in a single 8 byte frame
+    pub fn new(data_id: DataIdentifier, payload: &[u8]) -> Result<DataMessage, ZanCanError> {
+        if payload.is_empty() {
+            Err(ZanCanError::TruncatedData)
+        } else if payload.len() > MAX_DATA_LENGTH {
+            Err(ZanCanError::PayloadTooLarge)
         } else {
-            //So I decided not to validate the data just assume that if you put in the wrong length then you will get truncated data
-            Ok(DataMessage{message_data_len_bytes: m_d_len, data_identifier: data_id, data})
+            let mut data = [0u8; MAX_DATA_LENGTH];
+            let mut i: usize = 0;
+            while i < payload.len() {
+                data[i] = payload[i];
+                i += 1;
+            }
+            Ok(DataMessage{message_data_len_bytes: payload.len(), data_identifier: data_id, data})
         }
     }
 
+    //Fixed size version of new for messages whose payload size is known up front.
+    //A payload of 0 or more than MAX_PAYLOAD_LEN bytes fails to compile instead of erroring
+    pub fn with_payload<const N: usize>(data_id: DataIdentifier, payload: [u8; N]) -> DataMessage {
+        const { assert!(N >= 1 && N <= MAX_DATA_LENGTH, "DataMessage payload must be 1 to 4 bytes") };
+        let mut data = [0u8; MAX_DATA_LENGTH];
+        data[..N].copy_from_slice(&payload);
+        DataMessage{message_data_len_bytes: N, data_identifier: data_id, data}
+    }
+
+    //Opt in integrity check. The last payload byte is a CRC-8 over every wire byte before it, identifier included,
+    //so only MAX_PAYLOAD_LEN - 1 bytes are left for the payload itself
+    pub fn with_checksum(data_id: DataIdentifier, payload: &[u8]) -> Result<DataMessage, ZanCanError> {
+        if payload.len() >= MAX_DATA_LENGTH {
+            return Err(ZanCanError::PayloadTooLarge);
+        }
+        let mut data = [0u8; MAX_DATA_LENGTH];
+        data[..payload.len()].copy_from_slice(payload);
+        //An empty payload leaves just the checksum byte which is still a valid message
+        let mut message = DataMessage{message_data_len_bytes: payload.len() + 1, data_identifier: data_id, data};
+        message.data[payload.len()] = message.checksum();
+        Ok(message)
+    }
+
+    //CRC-8 of the wire bytes ahead of the last payload byte
+    fn checksum(&self) -> u8 {
+        let mut buff = [0u8; MAX_IDENTIFIER_LENGTH + MAX_DATA_LENGTH];
+        let len = self.write(&mut buff).expect("a message always fits in 8 bytes");
+        crc8(&buff[..len - 1])
+    }
+
+    //Only meaningful for messages built with with_checksum, anything else will almost always fail
+    pub fn verify_checksum(&self) -> bool {
+        self.data[self.message_data_len_bytes - 1] == self.checksum()
+    }
+
+    //Payload with the checksum byte stripped off, or ChecksumMismatch if the message was corrupted
+    pub fn checked_payload(&self) -> Result<&[u8], ZanCanError> {
+        if self.verify_checksum() {
+            Ok(&self.data[..self.message_data_len_bytes - 1])
+        } else {
+            Err(ZanCanError::ChecksumMismatch)
+        }
+    }
+
+    //Payload of a command byte followed by up to 3 value bytes, for nodes that take sub commands.
+    //Plain messages built with new are unaffected, the command byte is just the first payload byte
+    pub fn with_command(data_id: DataIdentifier, command: SetCommand, value: &[u8]) -> Result<DataMessage, ZanCanError> {
+        if value.len() >= MAX_DATA_LENGTH {
+            return Err(ZanCanError::PayloadTooLarge);
+        }
+        let mut data = [0u8; MAX_DATA_LENGTH];
+        data[0] = u8::from(command);
+        data[1..1 + value.len()].copy_from_slice(value);
+        Ok(DataMessage{message_data_len_bytes: value.len() + 1, data_identifier: data_id, data})
+    }
+
+    //First payload byte as a command, None if it isn't one. The wire doesn't mark command messages so this is
+    //only meaningful for identifiers the receiving node treats as taking commands
+    pub fn command(&self) -> Option<SetCommand> {
+        SetCommand::try_from(self.data[0]).ok()
+    }
+
+    //Payload after the command byte
+    pub fn command_value(&self) -> &[u8] {
+        &self.data[1..self.message_data_len_bytes]
+    }
+
+    //Byte order helpers for numeric payloads. Frames this library builds are big endian so prefer the _be
+    //versions unless a node already expects little endian. write replaces the payload, read takes the first bytes
+    pub fn write_u16_be(&mut self, value: u16) {
+        self.set_payload(&value.to_be_bytes());
+    }
+
+    pub fn write_u16_le(&mut self, value: u16) {
+        self.set_payload(&value.to_le_bytes());
+    }
+
+    pub fn write_u32_be(&mut self, value: u32) {
+        self.set_payload(&value.to_be_bytes());
+    }
+
+    pub fn write_u32_le(&mut self, value: u32) {
+        self.set_payload(&value.to_le_bytes());
+    }
+
+    pub fn read_u16_be(&self) -> Result<u16, ZanCanError> {
+        Ok(u16::from_be_bytes(self.leading_bytes()?))
+    }
+
+    pub fn read_u16_le(&self) -> Result<u16, ZanCanError> {
+        Ok(u16::from_le_bytes(self.leading_bytes()?))
+    }
+
+    pub fn read_u32_be(&self) -> Result<u32, ZanCanError> {
+        Ok(u32::from_be_bytes(self.leading_bytes()?))
+    }
+
+    pub fn read_u32_le(&self) -> Result<u32, ZanCanError> {
+        Ok(u32::from_le_bytes(self.leading_bytes()?))
+    }
+
+    //Typed values at a byte offset into the payload, always big endian. Writing past the current payload grows it,
+    //anything that would go past MAX_PAYLOAD_LEN gives PayloadTooLarge. Reading past the payload gives TruncatedData
+    pub fn write_u16(&mut self, offset: usize, value: u16) -> Result<(), ZanCanError> {
+        self.write_at(offset, &value.to_be_bytes())
+    }
+
+    pub fn write_i16(&mut self, offset: usize, value: i16) -> Result<(), ZanCanError> {
+        self.write_at(offset, &value.to_be_bytes())
+    }
+
+    pub fn write_u32(&mut self, offset: usize, value: u32) -> Result<(), ZanCanError> {
+        self.write_at(offset, &value.to_be_bytes())
+    }
+
+    pub fn write_i32(&mut self, offset: usize, value: i32) -> Result<(), ZanCanError> {
+        self.write_at(offset, &value.to_be_bytes())
+    }
+
+    pub fn read_u16(&self, offset: usize) -> Result<u16, ZanCanError> {
+        Ok(u16::from_be_bytes(self.read_at(offset)?))
+    }
+
+    pub fn read_i16(&self, offset: usize) -> Result<i16, ZanCanError> {
+        Ok(i16::from_be_bytes(self.read_at(offset)?))
+    }
+
+    pub fn read_u32(&self, offset: usize) -> Result<u32, ZanCanError> {
+        Ok(u32::from_be_bytes(self.read_at(offset)?))
+    }
+
+    pub fn read_i32(&self, offset: usize) -> Result<i32, ZanCanError> {
+        Ok(i32::from_be_bytes(self.read_at(offset)?))
+    }
+
+    fn write_at(&mut self, offset: usize, bytes: &[u8]) -> Result<(), ZanCanError> {
+        let end = match offset.checked_add(bytes.len()) {
+            Some(end) if end <= MAX_DATA_LENGTH => end,
+            _ => return Err(ZanCanError::PayloadTooLarge)
+        };
+        self.data[offset..end].copy_from_slice(bytes);
+        self.message_data_len_bytes = self.message_data_len_bytes.max(end);
+        Ok(())
+    }
+
+    fn read_at<const N: usize>(&self, offset: usize) -> Result<[u8; N], ZanCanError> {
+        let end = match offset.checked_add(N) {
+            Some(end) if end <= self.message_data_len_bytes => end,
+            _ => return Err(ZanCanError::TruncatedData)
+        };
+        let mut bytes = [0u8; N];
+        bytes.copy_from_slice(&self.data[offset..end]);
+        Ok(bytes)
+    }
+
+    fn set_payload(&mut self, payload: &[u8]) {
+        self.data = [0u8; MAX_DATA_LENGTH];
+        self.data[..payload.len()].copy_from_slice(payload);
+        self.message_data_len_bytes = payload.len();
+    }
+
+    //TruncatedData if the payload is shorter than N
+    fn leading_bytes<const N: usize>(&self) -> Result<[u8; N], ZanCanError> {
+        if self.message_data_len_bytes < N {
+            return Err(ZanCanError::TruncatedData);
+        }
+        let mut bytes = [0u8; N];
+        bytes.copy_from_slice(&self.data[..N]);
+        Ok(bytes)
+    }
+
     pub fn addr_len(&self) -> usize {
         self.data_identifier.len()
     }
@@ -105,6 +439,8 @@ impl DataMessage {
         self.message_data_len_bytes
     }
 
+    //A message always carries its identifier so it can never be empty
+    #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
         self.data_identifier.len() + self.message_data_len_bytes
     }
@@ -117,12 +453,21 @@ impl DataMessage {
         &self.data[..self.message_data_len_bytes]
     }
 
-    pub fn write(&self, buff: &mut [u8]) -> Result<usize, &'static str> {
+    //Same as data_identifier and data, named for routing by identifier then reading the payload
+    pub fn identifier(&self) -> DataIdentifier {
+        self.data_identifier
+    }
+
+    pub fn payload(&self) -> &[u8] {
+        self.data()
+    }
+
+    pub fn write(&self, buff: &mut [u8]) -> Result<usize, ZanCanError> {
         if buff.len() < self.data_identifier.len() + self.message_data_len_bytes {
-            Err("buffer too short to write DataMessage to")
+            Err(ZanCanError::BufferTooSmall)
         } else {
             self.data_identifier.write(&mut buff[0..self.data_identifier.len()])?;
-            buff[0] |= (self.message_data_len_bytes << 6) as u8;
+            buff[0] |= ((self.message_data_len_bytes - 1) << 6) as u8;
             
             let mut i: usize = 0;
             while i < self.message_data_len_bytes {
@@ -134,18 +479,27 @@ impl DataMessage {
     }
 }
 
+//Only the bytes in use are compared, the same as ZanCanFrame
+impl PartialEq for DataMessage {
+    fn eq(&self, other: &Self) -> bool {
+        self.data_identifier == other.data_identifier && self.data() == other.data()
+    }
+}
+
+impl Eq for DataMessage {}
+
 impl TryFrom<&[u8]> for DataMessage {
-    type Error = &'static str;
+    type Error = ZanCanError;
     fn try_from(buff: &[u8]) -> Result<Self, Self::Error> {
-        if buff.len() == 0 {
-            Err("unable to convert from &[u8] to DataMessage if buff is empty")
+        if buff.is_empty() {
+            Err(ZanCanError::TruncatedData)
         } else if buff.len() > MAX_DATA_LENGTH + MAX_IDENTIFIER_LENGTH {
-            Err("unable to convert from &[u8] to DataMessage due to oversized buffer")
+            Err(ZanCanError::OversizedData)
         } else {
-            let data_len: usize = ((buff[0] & 0xC0) >> 6) as usize;
+            let data_len: usize = payload_len_from_header(buff[0]);
 
-            if buff.len() == data_len {
-                Err("unable to convert from &[u8] to DataMessage due to buffer being less than required")
+            if buff.len() <= data_len {
+                Err(ZanCanError::TruncatedData)
             } else {
                 let id_len: usize = buff.len() - data_len;
                 let d_id = DataIdentifier::try_from(&buff[0..id_len])?;
@@ -0,0 +1,74 @@
+
+//Request/response plumbing most nodes share. Handlers say what the node holds, Node turns incoming frames into replies:
+//RequestData is answered with SentData, SetData is applied, and either gets a DataNak back when a handler refuses it
+
+use super::address::{ExtendedAddress, ZanCanAddress};
+use super::emergency::{EmergencyReason, EmergencyStatus};
+use super::message_data::{DataIdentifier, DataMessage, NakReason};
+use super::zan_can_type::ZanCanFrameType;
+use super::ZanCanFrame;
+use embedded_can::Frame;
+
+pub struct Node<R, S>
+where
+    R: FnMut(DataIdentifier) -> Result<DataMessage, NakReason>,
+    S: FnMut(&DataMessage) -> Result<(), NakReason>
+{
+    address: ZanCanAddress,
+    on_request: R,
+    on_set: S
+}
+
+impl<R, S> Node<R, S>
+where
+    R: FnMut(DataIdentifier) -> Result<DataMessage, NakReason>,
+    S: FnMut(&DataMessage) -> Result<(), NakReason>
+{
+    pub fn new(address: ZanCanAddress, on_request: R, on_set: S) -> Self {
+        Node { address, on_request, on_set }
+    }
+
+    pub fn address(&self) -> ZanCanAddress {
+        self.address
+    }
+
+    //Reply to send for a frame, if any. Frames for other nodes and frames that don't decode are ignored.
+    //A successful SetData has no reply. Extended frames must carry the whole address and are answered with an extended id
+    pub fn handle(&mut self, frame: &ZanCanFrame) -> Option<ZanCanFrame> {
+        let target = frame.extended_address();
+        if target != ExtendedAddress::from(self.address) && !target.is_broadcast() {
+            return None;
+        }
+        let reply = self.reply(frame)?;
+        if frame.is_extended() {
+            Some(reply.with_extended_address(ExtendedAddress::from(self.address)))
+        } else {
+            Some(reply)
+        }
+    }
+
+    fn reply(&mut self, frame: &ZanCanFrame) -> Option<ZanCanFrame> {
+        match frame.frame_type() {
+            ZanCanFrameType::RequestData => {
+                let data_id = frame.decode_request_data().ok()?;
+                match (self.on_request)(data_id) {
+                    Ok(message) => ZanCanFrame::try_new_sent_data(self.address, message).ok(),
+                    Err(reason) => Some(ZanCanFrame::new_data_nak(self.address, data_id, reason))
+                }
+            },
+            ZanCanFrameType::SetData => {
+                let message = frame.decode_set_data().ok()?;
+                match (self.on_set)(&message) {
+                    Ok(()) => None,
+                    Err(reason) => Some(ZanCanFrame::new_data_nak(self.address, message.data_identifier(), reason))
+                }
+            },
+            _ => None
+        }
+    }
+
+    //Emergency sent from this node
+    pub fn emergency(&self, status: EmergencyStatus, reason: EmergencyReason) -> ZanCanFrame {
+        ZanCanFrame::new_emergency(self.address, status, reason)
+    }
+}
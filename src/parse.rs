@@ -0,0 +1,196 @@
+
+//Small text format for building fixtures in scripted tests, one frame per line:
+//  EMERGENCY 0x12 Stop OverTemperature
+//  ERROR 0x21 BusOff [cleared]
+//  HEARTBEAT 0x42 1000
+//  SENT_DATA 0x05 0x0400 1 2     (SET_DATA the same, identifier then payload bytes)
+//  REQUEST_DATA 0x05 0x0400 [args...]
+//  DATA_NAK 0x05 0x0400 Busy
+//  TIME 0x01 1 2 3 4 5 6 7 8
+//Frame types can be the Display label or the variant name. Names match case insensitively and can be shortened
+//as long as only one name starts that way, so OverTemp works. Numbers are decimal or 0x hex
+
+use core::fmt;
+
+use super::address::ZanCanAddress;
+use super::emergency::{EmergencyReason, EmergencyStatus};
+use super::error::ErrorCode;
+use super::message_data::{DataIdentifier, DataMessage, NakReason};
+use super::zan_can_type::ZanCanFrameType;
+use super::{id_from_type_and_address, ZanCanFrame};
+
+//What went wrong and the token it went wrong at, empty if the line ran out early
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError<'a> {
+    pub message: &'static str,
+    pub token: &'a str
+}
+
+impl fmt::Display for ParseError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.token.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{} '{}'", self.message, self.token)
+        }
+    }
+}
+
+impl core::error::Error for ParseError<'_> {}
+
+const NAK_REASONS: [NakReason; 3] = [NakReason::UnsupportedIdentifier, NakReason::Busy, NakReason::AccessDenied];
+
+impl ZanCanFrame {
+    pub fn parse(line: &str) -> Result<ZanCanFrame, ParseError<'_>> {
+        let mut tokens = line.split_whitespace();
+        let f_type = lookup(next(&mut tokens, "missing frame type")?, &ZanCanFrameType::ALL, |t| t.name(), "unknown frame type")?;
+        let addr_token = next(&mut tokens, "missing address")?;
+        let addr = ZanCanAddress::from(u8::try_from(number(addr_token)?).map_err(|_| error("address does not fit in a byte", addr_token))?);
+
+        let frame = match f_type {
+            ZanCanFrameType::Emergency => {
+                let status_token = next(&mut tokens, "missing emergency status")?;
+                let status = match status_token {
+                    s if s.eq_ignore_ascii_case("stop") || s.eq_ignore_ascii_case("active") => EmergencyStatus::Stop,
+                    s if s.eq_ignore_ascii_case("clear") || s.eq_ignore_ascii_case("cleared") => EmergencyStatus::Clear,
+                    s => return Err(error("unknown emergency status", s))
+                };
+                let reason_token = next(&mut tokens, "missing emergency reason")?;
+                let reason = match number(reason_token) {
+                    Ok(value) => u16::try_from(value).ok().and_then(|v| EmergencyReason::try_from(v).ok()).ok_or(error("emergency reason does not fit in 15 bits", reason_token))?,
+                    Err(_) => lookup(reason_token, &EmergencyReason::ALL, |r| r.name(), "unknown emergency reason")?
+                };
+                ZanCanFrame::new_emergency(addr, status, reason)
+            },
+            ZanCanFrameType::Error => {
+                let code_token = next(&mut tokens, "missing error code")?;
+                let code = match number(code_token) {
+                    Ok(value) => ErrorCode::from(u16::try_from(value).map_err(|_| error("error code does not fit in 16 bits", code_token))?),
+                    Err(_) => lookup(code_token, &ErrorCode::ALL, |c| c.name(), "unknown error code")?
+                };
+                let cleared = match tokens.next() {
+                    None => false,
+                    Some(t) if t.eq_ignore_ascii_case("cleared") => true,
+                    Some(t) => return Err(error("expected cleared or nothing after the error code", t))
+                };
+                ZanCanFrame::new_error_with_status(addr, code, cleared)
+            },
+            ZanCanFrameType::Heartbeat => {
+                let uptime_token = next(&mut tokens, "missing uptime")?;
+                ZanCanFrame::new_heartbeat(addr, number(uptime_token)?)
+            },
+            ZanCanFrameType::SentData | ZanCanFrameType::SetData => {
+                let data_id = identifier(next(&mut tokens, "missing data identifier")?)?;
+                let (buff, len) = bytes(&mut tokens)?;
+                let message = DataMessage::new(data_id, &buff[..len]).map_err(|_| error("payload does not fit in a data message", line))?;
+                if f_type == ZanCanFrameType::SentData {
+                    ZanCanFrame::new_sent_data(addr, message)
+                } else {
+                    ZanCanFrame::new_set_data(addr, message)
+                }
+            },
+            ZanCanFrameType::RequestData => {
+                let data_id = identifier(next(&mut tokens, "missing data identifier")?)?;
+                let (buff, len) = bytes(&mut tokens)?;
+                ZanCanFrame::new_request_data_with_args(addr, data_id, &buff[..len]).map_err(|_| error("too many request arguments", line))?
+            },
+            ZanCanFrameType::DataNak => {
+                let data_id = identifier(next(&mut tokens, "missing data identifier")?)?;
+                let reason_token = next(&mut tokens, "missing nak reason")?;
+                let reason = match number(reason_token) {
+                    Ok(value) => NakReason::from(u8::try_from(value).map_err(|_| error("nak reason does not fit in a byte", reason_token))?),
+                    Err(_) => lookup(reason_token, &NAK_REASONS, nak_name, "unknown nak reason")?
+                };
+                ZanCanFrame::new_data_nak(addr, data_id, reason)
+            },
+            ZanCanFrameType::Time => {
+                let (buff, len) = bytes(&mut tokens)?;
+                let id = id_from_type_and_address(f_type, addr).expect("every frame type fits in a standard id with any address");
+                ZanCanFrame::new(id, &buff[..len]).expect("8 bytes or less always fit")
+            }
+        };
+
+        match tokens.next() {
+            Some(extra) => Err(error("unexpected token", extra)),
+            None => Ok(frame)
+        }
+    }
+}
+
+fn error<'a>(message: &'static str, token: &'a str) -> ParseError<'a> {
+    ParseError { message, token }
+}
+
+fn next<'a>(tokens: &mut impl Iterator<Item = &'a str>, message: &'static str) -> Result<&'a str, ParseError<'a>> {
+    tokens.next().ok_or(error(message, ""))
+}
+
+fn number(token: &str) -> Result<u32, ParseError<'_>> {
+    let parsed = match token.strip_prefix("0x").or_else(|| token.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => token.parse::<u32>()
+    };
+    parsed.map_err(|_| error("expected a number", token))
+}
+
+fn identifier(token: &str) -> Result<DataIdentifier, ParseError<'_>> {
+    DataIdentifier::try_from(number(token)?).map_err(|_| error("data identifier does not fit in 30 bits", token))
+}
+
+//Whatever is left on the line as bytes, at most 8
+fn bytes<'a>(tokens: &mut impl Iterator<Item = &'a str>) -> Result<([u8; 8], usize), ParseError<'a>> {
+    let mut buff = [0u8; 8];
+    let mut len: usize = 0;
+    for token in tokens {
+        if len == buff.len() {
+            return Err(error("more than 8 bytes", token));
+        }
+        buff[len] = u8::try_from(number(token)?).map_err(|_| error("byte is larger than 0xFF", token))?;
+        len += 1;
+    }
+    Ok((buff, len))
+}
+
+fn nak_name(reason: &NakReason) -> &'static str {
+    match reason {
+        NakReason::UnsupportedIdentifier => "UnsupportedIdentifier",
+        NakReason::Busy => "Busy",
+        NakReason::AccessDenied => "AccessDenied",
+        NakReason::Unknown(_) => "Unknown"
+    }
+}
+
+//An exact name wins, otherwise the token has to be the start of exactly one name
+fn lookup<'a, T: Copy>(token: &'a str, all: &[T], name: impl Fn(&T) -> &'static str, message: &'static str) -> Result<T, ParseError<'a>> {
+    if let Some(exact) = all.iter().find(|t| name_matches(token, name(t), false)) {
+        return Ok(*exact);
+    }
+    let mut found = None;
+    for t in all {
+        if name_matches(token, name(t), true) {
+            if found.is_some() {
+                return Err(error("ambiguous name", token));
+            }
+            found = Some(*t);
+        }
+    }
+    found.ok_or(error(message, token))
+}
+
+//Case insensitive and ignoring underscores in the token, so SENT_DATA matches SentData
+fn name_matches(token: &str, name: &str, prefix: bool) -> bool {
+    let mut token_chars = token.chars().filter(|c| *c != '_');
+    let mut name_chars = name.chars();
+    loop {
+        match (token_chars.next(), name_chars.next()) {
+            (None, None) => return true,
+            (None, Some(_)) => return prefix,
+            (Some(_), None) => return false,
+            (Some(t), Some(n)) => {
+                if !t.eq_ignore_ascii_case(&n) {
+                    return false;
+                }
+            }
+        }
+    }
+}
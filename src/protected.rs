@@ -0,0 +1,85 @@
+
+//End to end integrity for critical commands, e.g. a SetData that moves something. Opt in, both ends have to agree a
+//frame is protected since the CRC takes the last 2 data bytes and the frame won't decode until it is stripped off.
+//Unlike DataMessage::with_checksum this covers the id as well, so a message that ends up at the wrong address is caught
+
+use embedded_can::Frame;
+
+use super::error::ZanCanError;
+use super::ZanCanFrame;
+
+//Bytes the CRC takes at the end of the frame
+pub const CRC_LEN: usize = 2;
+//Largest payload that still leaves room for the CRC
+pub const MAX_PROTECTED_LEN: usize = 8 - CRC_LEN;
+
+//Set in the id bytes fed to the CRC for extended frames, so the same raw id in the other width doesn't match
+const EXTENDED_FLAG: u32 = 0x8000_0000;
+
+//A frame with a CRC-16 over its id and payload appended big endian
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProtectedFrame {
+    wire: ZanCanFrame
+}
+
+impl ProtectedFrame {
+    //Payload has to be MAX_PROTECTED_LEN bytes or less to make room for the CRC. RTR frames have no data to put the CRC in
+    //and give RemoteFrameNotSupported
+    pub fn new(frame: &ZanCanFrame) -> Result<ProtectedFrame, ZanCanError> {
+        if frame.is_remote_frame() {
+            return Err(ZanCanError::RemoteFrameNotSupported);
+        }
+        let payload = frame.raw_payload();
+        if payload.len() > MAX_PROTECTED_LEN {
+            return Err(ZanCanError::PayloadTooLarge);
+        }
+        let mut data = [0u8; 8];
+        data[..payload.len()].copy_from_slice(payload);
+        data[payload.len()..payload.len() + CRC_LEN].copy_from_slice(&frame_crc(frame, payload).to_be_bytes());
+        let wire = ZanCanFrame::new(frame.id(), &data[..payload.len() + CRC_LEN]).expect("protected frame keeps a valid id and fits in 8 bytes");
+        Ok(ProtectedFrame { wire })
+    }
+
+    //What goes on the bus
+    pub fn frame(&self) -> &ZanCanFrame {
+        &self.wire
+    }
+
+    //Checks a received frame and gives back the original without the CRC, ChecksumMismatch if anything changed
+    pub fn verify(wire: &ZanCanFrame) -> Result<ZanCanFrame, ZanCanError> {
+        let data = wire.raw_payload();
+        if data.len() < CRC_LEN {
+            return Err(ZanCanError::TruncatedData);
+        }
+        let (payload, crc) = data.split_at(data.len() - CRC_LEN);
+        if frame_crc(wire, payload).to_be_bytes() != crc {
+            return Err(ZanCanError::ChecksumMismatch);
+        }
+        Ok(ZanCanFrame::new(wire.id(), payload).expect("received frame already had a valid id"))
+    }
+}
+
+//CRC over the id as 4 big endian bytes then the payload
+fn frame_crc(frame: &ZanCanFrame, payload: &[u8]) -> u16 {
+    let mut id = frame.raw_id();
+    if frame.is_extended() {
+        id |= EXTENDED_FLAG;
+    }
+    let crc = crc16(0xFFFF, &id.to_be_bytes());
+    crc16(crc, payload)
+}
+
+//CRC-16/CCITT-FALSE: polynomial 0x1021, start value 0xFFFF, no reflection and no final xor
+fn crc16(mut crc: u16, bytes: &[u8]) -> u16 {
+    let mut i: usize = 0;
+    while i < bytes.len() {
+        crc ^= (bytes[i] as u16) << 8;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x1021 } else { crc << 1 };
+            bit += 1;
+        }
+        i += 1;
+    }
+    crc
+}
@@ -0,0 +1,135 @@
+
+//Segmented transfers for payloads that don't fit in one frame.
+//Each segment is a normal SentData or SetData frame whose identifier is the base identifier shifted up 8 bits
+//with the low byte holding the segment sequence number and a last segment flag
+
+use super::address::ZanCanAddress;
+use super::error::ZanCanError;
+use super::message_data::{DataIdentifier, DataMessage};
+use super::zan_can_type::ZanCanFrameType;
+use super::ZanCanFrame;
+
+//Payload bytes carried by each segment
+pub const SEGMENT_PAYLOAD_LEN: usize = DataMessage::MAX_PAYLOAD_LEN;
+//Sequence number is 7 bits
+pub const MAX_SEGMENTS: usize = 128;
+pub const MAX_SEGMENTED_PAYLOAD_LEN: usize = SEGMENT_PAYLOAD_LEN * MAX_SEGMENTS;
+//Base identifier has to leave room for the sequence byte within the 30 bits of a DataIdentifier
+pub const MAX_BASE_IDENTIFIER: u32 = 0x3F_FFFF;
+
+const LAST_SEGMENT_FLAG: u32 = 0x80;
+const SEQUENCE_MASK: u32 = 0x7F;
+
+//Splits payload into frames of the given type. Only SentData and SetData frames can carry segments
+pub fn segment_data(f_type: ZanCanFrameType, addr: ZanCanAddress, data_id: DataIdentifier, payload: &[u8]) -> Result<Segments<'_>, ZanCanError> {
+    if f_type != ZanCanFrameType::SentData && f_type != ZanCanFrameType::SetData {
+        Err(ZanCanError::UnsupportedFrameType(f_type))
+    } else if u32::from(data_id) > MAX_BASE_IDENTIFIER {
+        Err(ZanCanError::InvalidDataIdentifier)
+    } else if payload.is_empty() {
+        Err(ZanCanError::TruncatedData)
+    } else if payload.len() > MAX_SEGMENTED_PAYLOAD_LEN {
+        Err(ZanCanError::PayloadTooLarge)
+    } else {
+        Ok(Segments { f_type, addr, base_id: u32::from(data_id), payload, seq: 0 })
+    }
+}
+
+//Iterator over the frames of a segmented transfer, built by segment_data
+pub struct Segments<'a> {
+    f_type: ZanCanFrameType,
+    addr: ZanCanAddress,
+    base_id: u32,
+    payload: &'a [u8],
+    seq: usize
+}
+
+impl Iterator for Segments<'_> {
+    type Item = ZanCanFrame;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.seq * SEGMENT_PAYLOAD_LEN;
+        if offset >= self.payload.len() {
+            return None;
+        }
+        let end = (offset + SEGMENT_PAYLOAD_LEN).min(self.payload.len());
+        let mut seg_id = (self.base_id << 8) | self.seq as u32;
+        if end == self.payload.len() {
+            seg_id |= LAST_SEGMENT_FLAG;
+        }
+        self.seq += 1;
+
+        //segment_data already checked the base id and payload length so every segment is valid
+        let data_id = DataIdentifier::try_from(seg_id).expect("segment identifier should always fit in a DataIdentifier");
+        let message = DataMessage::new(data_id, &self.payload[offset..end]).expect("segment payload should always fit in a DataMessage");
+        let frame = match self.f_type {
+            ZanCanFrameType::SentData => ZanCanFrame::new_sent_data(self.addr, message),
+            _ => ZanCanFrame::new_set_data(self.addr, message)
+        };
+        Some(frame)
+    }
+}
+
+//Collects segments back into the original payload. N is the largest payload it can hold
+pub struct Reassembler<const N: usize> {
+    buff: [u8; N],
+    len: usize,
+    base_id: Option<u32>,
+    next_seq: u32
+}
+
+impl<const N: usize> Reassembler<N> {
+    pub fn new() -> Self {
+        Reassembler { buff: [0u8; N], len: 0, base_id: None, next_seq: 0 }
+    }
+
+    //Drops any transfer in progress
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.base_id = None;
+        self.next_seq = 0;
+    }
+
+    //Feeds a segment in. Returns the full payload once the last segment arrives.
+    //A segment that is out of order or skips one errors and drops the transfer in progress.
+    //Segment 0 always starts a new transfer
+    pub fn push(&mut self, frame: &ZanCanFrame) -> Result<Option<&[u8]>, ZanCanError> {
+        if frame.frame_type() != ZanCanFrameType::SentData && frame.frame_type() != ZanCanFrameType::SetData {
+            return Err(ZanCanError::UnsupportedFrameType(frame.frame_type()));
+        }
+        let seg_id = u32::from(frame.data_identifier()?);
+        let base_id = seg_id >> 8;
+        let seq = seg_id & SEQUENCE_MASK;
+        let payload = frame.data_payload();
+
+        if seq == 0 {
+            self.reset();
+            self.base_id = Some(base_id);
+        } else if self.base_id != Some(base_id) || seq != self.next_seq {
+            self.reset();
+            return Err(ZanCanError::SegmentOutOfOrder);
+        }
+
+        if self.len + payload.len() > N {
+            self.reset();
+            return Err(ZanCanError::BufferTooSmall);
+        }
+        self.buff[self.len..self.len + payload.len()].copy_from_slice(payload);
+        self.len += payload.len();
+        self.next_seq = seq + 1;
+
+        if seg_id & LAST_SEGMENT_FLAG != 0 {
+            let total = self.len;
+            self.reset();
+            Ok(Some(&self.buff[..total]))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<const N: usize> Default for Reassembler<N> {
+    fn default() -> Self {
+        Reassembler::new()
+    }
+}
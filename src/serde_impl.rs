@@ -0,0 +1,136 @@
+
+//serde support for the types that can't just derive it. Only significant bytes are serialized and
+//everything is validated on the way back in the same as if it came off the bus
+
+use core::fmt;
+
+use embedded_can::{Frame, Id, StandardId};
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{self, Serialize, SerializeStruct, Serializer};
+
+use super::emergency::EmergencyReason;
+use super::message_data::{DataIdentifier, DataMessage};
+use super::zan_can_type::ZanCanFrameType;
+use super::ZanCanFrame;
+
+impl Serialize for ZanCanFrame {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.is_remote_frame() {
+            return Err(ser::Error::custom("remote frames are not supported"));
+        }
+        let raw_id = match self.id {
+            Id::Standard(sid) => sid.as_raw(),
+            Id::Extended(_) => return Err(ser::Error::custom("extended ids are not supported"))
+        };
+        let mut st = serializer.serialize_struct("ZanCanFrame", 3)?;
+        st.serialize_field("id", &raw_id)?;
+        st.serialize_field("frame_type", &self.f_type)?;
+        st.serialize_field("data", self.data())?;
+        st.end()
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename = "ZanCanFrame")]
+struct FrameRepr {
+    id: u16,
+    frame_type: ZanCanFrameType,
+    data: Bytes<8>
+}
+
+impl<'de> Deserialize<'de> for ZanCanFrame {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = FrameRepr::deserialize(deserializer)?;
+        let sid = StandardId::new(repr.id).ok_or_else(|| de::Error::custom("id does not fit in 11 bits"))?;
+        let frame = ZanCanFrame::new(sid, repr.data.as_slice()).ok_or_else(|| de::Error::custom("id does not hold a known frame type"))?;
+        if frame.f_type != repr.frame_type {
+            return Err(de::Error::custom("frame_type does not match the type in the id"));
+        }
+        Ok(frame)
+    }
+}
+
+impl<'de> Deserialize<'de> for EmergencyReason {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        EmergencyReason::try_from(u16::deserialize(deserializer)?).map_err(|_| de::Error::custom("reason does not fit in 15 bits"))
+    }
+}
+
+impl<'de> Deserialize<'de> for DataIdentifier {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        DataIdentifier::try_from(u32::deserialize(deserializer)?).map_err(|_| de::Error::custom("data identifier does not fit in 30 bits"))
+    }
+}
+
+impl Serialize for DataMessage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut st = serializer.serialize_struct("DataMessage", 2)?;
+        st.serialize_field("data_identifier", &self.data_identifier())?;
+        st.serialize_field("payload", self.data())?;
+        st.end()
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename = "DataMessage")]
+struct DataMessageRepr {
+    data_identifier: DataIdentifier,
+    payload: Bytes<4>
+}
+
+impl<'de> Deserialize<'de> for DataMessage {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = DataMessageRepr::deserialize(deserializer)?;
+        DataMessage::new(repr.data_identifier, repr.payload.as_slice()).map_err(|_| de::Error::custom("payload must be 1 to 4 bytes"))
+    }
+}
+
+//Up to N bytes deserialized without an allocator
+struct Bytes<const N: usize> {
+    buff: [u8; N],
+    len: usize
+}
+
+impl<const N: usize> Bytes<N> {
+    fn as_slice(&self) -> &[u8] {
+        &self.buff[..self.len]
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for Bytes<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(BytesVisitor::<N>)
+    }
+}
+
+struct BytesVisitor<const N: usize>;
+
+impl<'de, const N: usize> Visitor<'de> for BytesVisitor<N> {
+    type Value = Bytes<N>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "at most {} bytes", N)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        if v.len() > N {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        let mut buff = [0u8; N];
+        buff[..v.len()].copy_from_slice(v);
+        Ok(Bytes { buff, len: v.len() })
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut buff = [0u8; N];
+        let mut len: usize = 0;
+        while let Some(b) = seq.next_element::<u8>()? {
+            if len == N {
+                return Err(de::Error::invalid_length(len + 1, &self));
+            }
+            buff[len] = b;
+            len += 1;
+        }
+        Ok(Bytes { buff, len })
+    }
+}
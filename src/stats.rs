@@ -0,0 +1,67 @@
+
+//Running tallies of bus traffic for health monitoring. One counter per frame type and per standard address, so the
+//size doesn't grow with the number of nodes on the bus
+
+use embedded_can::Frame;
+
+use super::address::{ZanCanAddress, MAX_ADDRESS};
+use super::zan_can_type::ZanCanFrameType;
+use super::ZanCanFrame;
+
+const TYPE_COUNT: usize = ZanCanFrameType::ALL.len();
+const ADDRESS_COUNT: usize = MAX_ADDRESS as usize + 1;
+
+//Counters saturate instead of wrapping so a long running monitor never shows a count going backwards
+#[derive(Debug, Clone)]
+pub struct BusStats {
+    total: u32,
+    per_type: [u32; TYPE_COUNT],
+    per_address: [u32; ADDRESS_COUNT],
+    //Extended frames whose address is past the per address table
+    wide_addresses: u32
+}
+
+impl BusStats {
+    pub fn new() -> Self {
+        BusStats { total: 0, per_type: [0; TYPE_COUNT], per_address: [0; ADDRESS_COUNT], wide_addresses: 0 }
+    }
+
+    //Every frame counts towards total and its type. Extended frames whose address doesn't fit in the per address table
+    //are counted in count_from_wide_addresses instead of under their low byte
+    pub fn record(&mut self, frame: &ZanCanFrame) {
+        self.total = self.total.saturating_add(1);
+        let t = &mut self.per_type[u8::from(frame.frame_type()) as usize];
+        *t = t.saturating_add(1);
+        let a = match ZanCanAddress::try_from(frame.id()) {
+            Ok(addr) => &mut self.per_address[usize::from(addr)],
+            Err(_) => &mut self.wide_addresses
+        };
+        *a = a.saturating_add(1);
+    }
+
+    pub fn total(&self) -> u32 {
+        self.total
+    }
+
+    pub fn count(&self, frame_type: ZanCanFrameType) -> u32 {
+        self.per_type[u8::from(frame_type) as usize]
+    }
+
+    pub fn count_from(&self, addr: ZanCanAddress) -> u32 {
+        self.per_address[usize::from(addr)]
+    }
+
+    pub fn count_from_wide_addresses(&self) -> u32 {
+        self.wide_addresses
+    }
+
+    pub fn reset(&mut self) {
+        *self = BusStats::new();
+    }
+}
+
+impl Default for BusStats {
+    fn default() -> Self {
+        BusStats::new()
+    }
+}
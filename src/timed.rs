@@ -0,0 +1,41 @@
+
+//Pairs a frame with when it was seen, for logging traffic and latency analysis.
+//The timestamp type is up to the caller, a tick count, a u64 of micros or a driver's own instant type
+
+use core::ops::Deref;
+
+use super::ZanCanFrame;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TimedFrame<T> {
+    frame: ZanCanFrame,
+    timestamp: T
+}
+
+impl<T> TimedFrame<T> {
+    pub fn new(frame: ZanCanFrame, timestamp: T) -> TimedFrame<T> {
+        TimedFrame { frame, timestamp }
+    }
+
+    pub fn frame(&self) -> &ZanCanFrame {
+        &self.frame
+    }
+
+    pub fn timestamp(&self) -> &T {
+        &self.timestamp
+    }
+
+    pub fn into_parts(self) -> (ZanCanFrame, T) {
+        (self.frame, self.timestamp)
+    }
+}
+
+//Lets a timed frame be used anywhere a frame is read from, e.g. timed.decode()
+impl<T> Deref for TimedFrame<T> {
+    type Target = ZanCanFrame;
+
+    fn deref(&self) -> &ZanCanFrame {
+        &self.frame
+    }
+}
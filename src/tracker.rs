@@ -0,0 +1,119 @@
+
+//Keeps track of which faults are currently in effect on the bus, for supervisors that need more than the last frame.
+//Once full a new fault is refused with BufferTooSmall rather than pushing out one that is still in effect
+
+use super::address::ZanCanAddress;
+use super::emergency::EmergencyReason;
+use super::error::{ErrorCode, ZanCanError};
+use super::ZanCanFrame;
+use embedded_can::Frame;
+
+//Active emergencies per node. N is how many (address, reason) pairs can be active at once
+pub struct EmergencyTracker<const N: usize> {
+    active: [Option<(ZanCanAddress, EmergencyReason)>; N]
+}
+
+impl<const N: usize> EmergencyTracker<N> {
+    pub fn new() -> Self {
+        EmergencyTracker { active: [None; N] }
+    }
+
+    //Feeds an emergency frame in. Stop adds the reason for that node and Clear removes it.
+    //Errors if the frame isn't a valid emergency or there's no room left for another active reason. Extended frames
+    //whose address doesn't fit in a ZanCanAddress give ExtendedIdNotSupported rather than landing on their low byte
+    pub fn record(&mut self, frame: &ZanCanFrame) -> Result<(), ZanCanError> {
+        let (status, reason) = frame.decode_emergency()?;
+        let entry = (ZanCanAddress::try_from(frame.id())?, reason);
+        let existing = self.active.iter().position(|a| *a == Some(entry));
+
+        if status.is_active() {
+            if existing.is_none() {
+                let slot = self.active.iter_mut().find(|a| a.is_none()).ok_or(ZanCanError::BufferTooSmall)?;
+                *slot = Some(entry);
+            }
+        } else if let Some(i) = existing {
+            self.active[i] = None;
+        }
+        Ok(())
+    }
+
+    pub fn active_reasons(&self, addr: ZanCanAddress) -> impl Iterator<Item = EmergencyReason> + '_ {
+        self.active.iter().filter_map(move |a| match a {
+            Some((a_addr, reason)) if *a_addr == addr => Some(*reason),
+            _ => None
+        })
+    }
+
+    pub fn is_active(&self, addr: ZanCanAddress) -> bool {
+        self.active_reasons(addr).next().is_some()
+    }
+
+    pub fn any_active(&self) -> bool {
+        self.active.iter().any(|a| a.is_some())
+    }
+
+    pub fn clear(&mut self) {
+        self.active = [None; N];
+    }
+}
+
+impl<const N: usize> Default for EmergencyTracker<N> {
+    fn default() -> Self {
+        EmergencyTracker::new()
+    }
+}
+
+//Raised error codes per node, the Error frame version of EmergencyTracker. Frames without the cleared byte count as raised
+pub struct ErrorTracker<const N: usize> {
+    active: [Option<(ZanCanAddress, ErrorCode)>; N]
+}
+
+impl<const N: usize> ErrorTracker<N> {
+    pub fn new() -> Self {
+        ErrorTracker { active: [None; N] }
+    }
+
+    //Feeds an error frame in. Raised codes are added for that node and cleared ones removed.
+    //Errors if the frame isn't a valid error frame or there's no room left for another active code. Extended addresses
+    //are handled the same as EmergencyTracker::record
+    pub fn record(&mut self, frame: &ZanCanFrame) -> Result<(), ZanCanError> {
+        let (code, cleared) = frame.decode_error_status()?;
+        let entry = (ZanCanAddress::try_from(frame.id())?, code);
+        let existing = self.active.iter().position(|a| *a == Some(entry));
+
+        if !cleared {
+            if existing.is_none() {
+                let slot = self.active.iter_mut().find(|a| a.is_none()).ok_or(ZanCanError::BufferTooSmall)?;
+                *slot = Some(entry);
+            }
+        } else if let Some(i) = existing {
+            self.active[i] = None;
+        }
+        Ok(())
+    }
+
+    pub fn active_errors(&self, addr: ZanCanAddress) -> impl Iterator<Item = ErrorCode> + '_ {
+        self.active.iter().filter_map(move |a| match a {
+            Some((a_addr, code)) if *a_addr == addr => Some(*code),
+            _ => None
+        })
+    }
+
+    pub fn is_active(&self, addr: ZanCanAddress) -> bool {
+        self.active_errors(addr).next().is_some()
+    }
+
+    pub fn any_active(&self) -> bool {
+        self.active.iter().any(|a| a.is_some())
+    }
+
+    pub fn clear(&mut self) {
+        self.active = [None; N];
+    }
+}
+
+impl<const N: usize> Default for ErrorTracker<N> {
+    fn default() -> Self {
+        ErrorTracker::new()
+    }
+}
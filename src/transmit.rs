@@ -0,0 +1,27 @@
+
+//Sending frames through any embedded-can driver without converting at every call site
+
+use embedded_can::blocking;
+
+use super::ZanCanFrame;
+
+//Either the driver's frame type couldn't hold the frame, or the driver itself failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransmitError<E> {
+    UnsupportedFrame,
+    Can(E)
+}
+
+//Converts with to_frame and blocks until the driver has taken the frame
+pub fn transmit<C: blocking::Can>(frame: &ZanCanFrame, can: &mut C) -> Result<(), TransmitError<C::Error>> {
+    let driver_frame: C::Frame = frame.to_frame().ok_or(TransmitError::UnsupportedFrame)?;
+    can.transmit(&driver_frame).map_err(TransmitError::Can)
+}
+
+//Non blocking version. WouldBlock is passed through so it can be retried, and like the driver it gives back
+//any lower priority frame that got pushed out of the transmit buffer
+#[cfg(feature = "nb")]
+pub fn transmit_nb<C: embedded_can::nb::Can>(frame: &ZanCanFrame, can: &mut C) -> nb::Result<Option<C::Frame>, TransmitError<C::Error>> {
+    let driver_frame: C::Frame = frame.to_frame().ok_or(nb::Error::Other(TransmitError::UnsupportedFrame))?;
+    can.transmit(&driver_frame).map_err(|e| e.map(TransmitError::Can))
+}
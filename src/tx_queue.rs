@@ -0,0 +1,83 @@
+
+//Software transmit queue for drivers without hardware priority ordering.
+//pop gives back frames in the order they would win arbitration on the bus
+
+use super::error::ZanCanError;
+use super::ZanCanFrame;
+
+pub struct TxQueue<const N: usize> {
+    frames: [Option<ZanCanFrame>; N],
+    len: usize
+}
+
+impl<const N: usize> TxQueue<N> {
+    pub fn new() -> Self {
+        TxQueue { frames: [None; N], len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    pub fn clear(&mut self) {
+        self.frames = [None; N];
+        self.len = 0;
+    }
+
+    pub fn push(&mut self, frame: ZanCanFrame) -> Result<(), ZanCanError> {
+        if self.is_full() {
+            return Err(ZanCanError::QueueFull);
+        }
+        self.frames[self.len] = Some(frame);
+        self.len += 1;
+        Ok(())
+    }
+
+    //Lowest id first, frames with the same id come out in the order they were pushed
+    pub fn pop(&mut self) -> Option<ZanCanFrame> {
+        let index = self.next_index()?;
+        let frame = self.frames[index];
+        //Shift the rest down so push order is kept for ties
+        let mut i = index;
+        while i + 1 < self.len {
+            self.frames[i] = self.frames[i + 1];
+            i += 1;
+        }
+        self.len -= 1;
+        self.frames[self.len] = None;
+        frame
+    }
+
+    pub fn peek(&self) -> Option<&ZanCanFrame> {
+        self.frames[self.next_index()?].as_ref()
+    }
+
+    fn next_index(&self) -> Option<usize> {
+        let mut best: Option<(usize, (u8, u32))> = None;
+        let mut i: usize = 0;
+        while i < self.len {
+            if let Some(frame) = &self.frames[i] {
+                let key = (frame.priority(), frame.raw_id());
+                if best.is_none_or(|(_, best_key)| key < best_key) {
+                    best = Some((i, key));
+                }
+            }
+            i += 1;
+        }
+        best.map(|(index, _)| index)
+    }
+}
+
+impl<const N: usize> Default for TxQueue<N> {
+    fn default() -> Self {
+        TxQueue::new()
+    }
+}
@@ -1,43 +1,170 @@
 
-use embedded_can::Id;
+use embedded_can::{ExtendedId, Id, StandardId};
 
-use super::address::ADDRESS_BIT_LENGTH;
+use super::address::{ADDRESS_BIT_LENGTH, EXTENDED_ADDRESS_BIT_LENGTH, MAX_ADDRESS, MAX_EXTENDED_ADDRESS};
+use super::error::ZanCanError;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ZanCanFrameType {
     //Emergency is a stop the world event. Meant for safety. Should have highest priority
     Emergency,
+    //Negative answer to a RequestData, the node doesn't have or can't give the data asked for
+    DataNak,
     Error,
     Time,
     SentData,
     RequestData,
-    SetData
+    SetData,
+    //Periodic node alive announcement. Lowest priority
+    Heartbeat
+}
+
+//Ordered by arbitration, the type that wins the bus sorts first. So Emergency < Error < Heartbeat
+impl PartialOrd for ZanCanFrameType {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ZanCanFrameType {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.priority().cmp(&other.priority())
+    }
 }
 
 impl From<ZanCanFrameType> for u8 {
     fn from(t: ZanCanFrameType) -> u8 {
-        match t {
+        t.code()
+    }
+}
+
+//Type and address share the id so the largest type code above the largest address has to still fit in the id.
+//Checked at compile time so adding a frame type with too big a code breaks the build instead of corrupting ids
+const _: () = {
+    let mut max_code: u8 = 0;
+    let mut i = 0;
+    while i < ZanCanFrameType::ALL.len() {
+        if ZanCanFrameType::ALL[i].code() > max_code {
+            max_code = ZanCanFrameType::ALL[i].code();
+        }
+        i += 1;
+    }
+    let standard = ((max_code as u16) << ADDRESS_BIT_LENGTH) | MAX_ADDRESS as u16;
+    assert!(StandardId::new(standard).is_some(), "type codes collide with the address bits of a standard id");
+    let extended = ((max_code as u32) << EXTENDED_ADDRESS_BIT_LENGTH) | MAX_EXTENDED_ADDRESS;
+    assert!(ExtendedId::new(extended).is_some(), "type codes collide with the address bits of an extended id");
+};
+
+impl ZanCanFrameType {
+    //Every frame type, in type code order. New variants need adding here too
+    pub const ALL: [ZanCanFrameType; 8] = [
+        ZanCanFrameType::Emergency,
+        ZanCanFrameType::DataNak,
+        ZanCanFrameType::Error,
+        ZanCanFrameType::Time,
+        ZanCanFrameType::SentData,
+        ZanCanFrameType::RequestData,
+        ZanCanFrameType::SetData,
+        ZanCanFrameType::Heartbeat
+    ];
+
+    pub fn all() -> impl Iterator<Item = ZanCanFrameType> {
+        ZanCanFrameType::ALL.into_iter()
+    }
+
+    //DataNak, SentData, RequestData and SetData. Matches are exhaustive so new variants have to pick a group
+    pub fn is_data(&self) -> bool {
+        match self {
+            ZanCanFrameType::DataNak | ZanCanFrameType::SentData | ZanCanFrameType::RequestData | ZanCanFrameType::SetData => true,
+            ZanCanFrameType::Emergency | ZanCanFrameType::Error | ZanCanFrameType::Time | ZanCanFrameType::Heartbeat => false
+        }
+    }
+
+    //Emergency and Error
+    pub fn is_fault(&self) -> bool {
+        match self {
+            ZanCanFrameType::Emergency | ZanCanFrameType::Error => true,
+            ZanCanFrameType::DataNak | ZanCanFrameType::Time | ZanCanFrameType::SentData | ZanCanFrameType::RequestData | ZanCanFrameType::SetData | ZanCanFrameType::Heartbeat => false
+        }
+    }
+
+    //Data length the constructors produce, None for frame types whose payload length varies.
+    //Decoders take frames at least this long so a sender can append bytes later without breaking old receivers
+    pub fn expected_len(&self) -> Option<usize> {
+        match self {
+            ZanCanFrameType::Emergency | ZanCanFrameType::Error => Some(2),
+            ZanCanFrameType::Heartbeat => Some(4),
+            ZanCanFrameType::Time => Some(8),
+            ZanCanFrameType::DataNak | ZanCanFrameType::SentData | ZanCanFrameType::RequestData | ZanCanFrameType::SetData => None
+        }
+    }
+
+    //Stable label for logs, same as the variant name without needing Debug formatting
+    pub const fn name(&self) -> &'static str {
+        match self {
+            ZanCanFrameType::Emergency => "Emergency",
+            ZanCanFrameType::DataNak => "DataNak",
+            ZanCanFrameType::Error => "Error",
+            ZanCanFrameType::Time => "Time",
+            ZanCanFrameType::SentData => "SentData",
+            ZanCanFrameType::RequestData => "RequestData",
+            ZanCanFrameType::SetData => "SetData",
+            ZanCanFrameType::Heartbeat => "Heartbeat"
+        }
+    }
+
+    //Bit for this type in a supported types mask, see ZanCanFrame::new_capability. The 8 type codes fill a u8 exactly
+    pub const fn mask_bit(&self) -> u8 {
+        1 << self.code()
+    }
+
+    //Bus arbitration priority, lower is more urgent. The type code is the top of the id and the lowest id wins
+    //arbitration, so this is just the type code. Emergency is 0 and Heartbeat is the least urgent
+    pub fn priority(&self) -> u8 {
+        self.code()
+    }
+
+    //Type code that goes in the id bits above the address
+    pub(crate) const fn code(&self) -> u8 {
+        match self {
             ZanCanFrameType::Emergency => 0x00,
+            ZanCanFrameType::DataNak => 0x01,
             ZanCanFrameType::Error => 0x02,
             ZanCanFrameType::Time => 0x03,
             ZanCanFrameType::SentData => 0x04,
             ZanCanFrameType::RequestData => 0x05,
-            ZanCanFrameType::SetData => 0x06
+            ZanCanFrameType::SetData => 0x06,
+            ZanCanFrameType::Heartbeat => 0x07
+        }
+    }
+
+    //Checked lookup for a raw type code from somewhere other than an id, e.g. a config file or a log.
+    //Codes above the 3 bits of the id are reserved and give UnknownFrameType rather than panicking like From<u8>
+    pub fn try_from_code(code: u8) -> Result<ZanCanFrameType, ZanCanError> {
+        ZanCanFrameType::from_code(code).ok_or(ZanCanError::UnknownFrameType(code))
+    }
+
+    //Non panicking lookup of a type code, None if the code isn't one we know about
+    pub(crate) fn from_code(code: u8) -> Option<ZanCanFrameType> {
+        match code {
+            0x00 => Some(Self::Emergency),
+            0x01 => Some(Self::DataNak),
+            0x02 => Some(Self::Error),
+            0x03 => Some(Self::Time),
+            0x04 => Some(Self::SentData),
+            0x05 => Some(Self::RequestData),
+            0x06 => Some(Self::SetData),
+            0x07 => Some(Self::Heartbeat),
+            _ => None
         }
     }
 }
 
 impl From<u8> for ZanCanFrameType {
     fn from(value: u8) -> Self {
-        match value {
-            0x00 => Self::Emergency,
-            0x02 => Self::Error,
-            0x03 => Self::Time,
-            0x04 => Self::SentData,
-            0x05 => Self::RequestData,
-            0x06 => Self::SetData,
-            _ => panic!("Got an invalid value for from u8 to ZanCanFrameType")
-        }
+        ZanCanFrameType::from_code(value).expect("Got an invalid value for from u8 to ZanCanFrameType")
     }
 }
 
@@ -53,16 +180,19 @@ impl From<u32> for ZanCanFrameType {
     }
 }
 
-impl From<Id> for ZanCanFrameType{
-    fn from(id: Id) -> Self {
+impl TryFrom<Id> for ZanCanFrameType {
+    type Error = ZanCanError;
+
+    fn try_from(id: Id) -> Result<Self, ZanCanError> {
         match id {
             Id::Standard(sid) => {
                 //Strip the address off by bitshifting
-                ZanCanFrameType::from(sid.as_raw() >> ADDRESS_BIT_LENGTH)
+                ZanCanFrameType::try_from_code((sid.as_raw() >> ADDRESS_BIT_LENGTH) as u8)
             },
             Id::Extended(eid) => {
-                ZanCanFrameType::from(eid.as_raw() >> ADDRESS_BIT_LENGTH)
+                //Extended ids have the same type code above a wider address
+                ZanCanFrameType::try_from_code((eid.as_raw() >> EXTENDED_ADDRESS_BIT_LENGTH) as u8)
             }
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,77 @@
+use zan_can::{address::{AddressFilter, ExtendedAddress, ZanCanAddress, MAX_ADDRESS}, error::{ErrorCode, ZanCanError}, ZanCanFrame};
+
+#[test]
+fn test_max_address_round_trips_through_frame() {
+    let addr = ZanCanAddress::new(MAX_ADDRESS).expect("expected max address to be valid");
+
+    let frame = ZanCanFrame::new_error(addr, ErrorCode::from(0x0001));
+
+    assert_eq!(frame.address(), addr);
+    assert_eq!(frame.frame_type(), zan_can::zan_can_type::ZanCanFrameType::Error);
+}
+
+#[test]
+fn test_broadcast_emergency_reports_broadcast_address() {
+    let reason = zan_can::emergency::EmergencyReason::try_from(0x0001).expect("error generating reason");
+
+    let frame = ZanCanFrame::new_emergency(ZanCanAddress::BROADCAST, zan_can::emergency::EmergencyStatus::Stop, reason);
+
+    assert!(frame.address().is_broadcast());
+    assert!(!ZanCanAddress::from(0x01).is_broadcast());
+}
+
+const MOTOR_ADDR: ZanCanAddress = ZanCanAddress::from_const(0x12);
+static ROUTES: [ZanCanAddress; 2] = [MOTOR_ADDR, ZanCanAddress::from_const(0x13)];
+
+#[test]
+fn test_const_addresses_match_runtime_addresses() {
+    assert_eq!(MOTOR_ADDR, ZanCanAddress::from(0x12));
+    assert_eq!(ROUTES[1], ZanCanAddress::from(0x13));
+}
+
+#[test]
+fn test_address_filter_range_and_mask() {
+    let range = AddressFilter::range(ZanCanAddress::from(0x10), ZanCanAddress::from(0x1F));
+    let mask = AddressFilter::mask(0x20, 0xF0);
+    let frame = ZanCanFrame::new_error(ZanCanAddress::from(0x25), ErrorCode::BusOff);
+
+    assert!(range.contains(ZanCanAddress::from(0x10)));
+    assert!(range.contains(ZanCanAddress::from(0x1F)));
+    assert!(!range.contains(ZanCanAddress::from(0x20)));
+    assert!(!range.matches(&frame));
+    assert!(mask.matches(&frame));
+    assert!(!mask.contains(ZanCanAddress::from(0x35)));
+}
+
+#[test]
+fn test_address_filter_uses_the_whole_extended_address() {
+    let range = AddressFilter::range(ZanCanAddress::from(0x10), ZanCanAddress::from(0x1F));
+    let frame = ZanCanFrame::new_error(ZanCanAddress::from(0x15), ErrorCode::BusOff);
+
+    assert!(range.matches(&frame.with_extended_address(ExtendedAddress::from_const(0x15))));
+    //Low byte is in range but the node isn't
+    assert!(!range.matches(&frame.with_extended_address(ExtendedAddress::from_const(0x0115))));
+}
+
+#[test]
+fn test_addresses_order_by_value() {
+    const SEGMENT_B_START: ZanCanAddress = ZanCanAddress::from_const(0x80);
+
+    assert!(ZanCanAddress::from(0x10) < SEGMENT_B_START);
+    assert!(ZanCanAddress::from(0x80) >= SEGMENT_B_START);
+    assert!(ZanCanAddress::BROADCAST < ZanCanAddress::from(0x01));
+    assert_eq!(SEGMENT_B_START.value(), 0x80);
+    assert_eq!(ZanCanAddress::from(0x42).max(ZanCanAddress::from(0x24)), ZanCanAddress::from(0x42));
+}
+
+#[test]
+fn test_address_converts_to_and_from_wider_integers() {
+    let addr = ZanCanAddress::from(0x42);
+    let mut table = [0u8; MAX_ADDRESS as usize + 1];
+    table[usize::from(addr)] = 1;
+
+    assert_eq!(table[0x42], 1);
+    assert_eq!(u16::from(addr), 0x42);
+    assert_eq!(ZanCanAddress::try_from(0x00FFu16), Ok(ZanCanAddress::from(MAX_ADDRESS)));
+    assert_eq!(ZanCanAddress::try_from(0x0100u16), Err(ZanCanError::InvalidId));
+}
@@ -0,0 +1,56 @@
+//Shared between test files, each one only uses part of it
+#![allow(dead_code)]
+
+use embedded_can::{Frame, Id};
+
+//Stand in for a frame coming from a driver. It can hold more bytes than classic CAN allows
+//and can report a dlc that doesn't match its data, like a buggy driver might
+pub struct DriverFrame {
+    id: Id,
+    data: [u8; 64],
+    data_len: usize,
+    dlc: usize,
+    remote: bool
+}
+
+impl DriverFrame {
+    pub fn with_dlc(mut self, dlc: usize) -> Self {
+        self.dlc = dlc;
+        self
+    }
+}
+
+impl Frame for DriverFrame {
+    fn new(id: impl Into<Id>, data: &[u8]) -> Option<Self> {
+        if data.len() > 64 {
+            return None;
+        }
+        let mut buff = [0u8; 64];
+        buff[..data.len()].copy_from_slice(data);
+        Some(DriverFrame { id: id.into(), data: buff, data_len: data.len(), dlc: data.len(), remote: false })
+    }
+
+    fn new_remote(id: impl Into<Id>, dlc: usize) -> Option<Self> {
+        Some(DriverFrame { id: id.into(), data: [0u8; 64], data_len: 0, dlc, remote: true })
+    }
+
+    fn is_extended(&self) -> bool {
+        matches!(self.id, Id::Extended(_))
+    }
+
+    fn is_remote_frame(&self) -> bool {
+        self.remote
+    }
+
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn dlc(&self) -> usize {
+        self.dlc
+    }
+
+    fn data(&self) -> &[u8] {
+        &self.data[..self.data_len]
+    }
+}
@@ -0,0 +1,34 @@
+#![cfg(feature = "dbc")]
+
+use zan_can::{dbc::{ByteOrder, DbcMessage, Signal}, ZanCanFrame};
+
+const VOLTAGE: Signal = Signal { name: "Voltage", start_bit: 0, length: 16, byte_order: ByteOrder::Intel, signed: false, factor: 0.001, offset: 0.0 };
+const TEMPERATURE: Signal = Signal { name: "Temperature", start_bit: 23, length: 8, byte_order: ByteOrder::Motorola, signed: true, factor: 1.0, offset: 0.0 };
+
+#[test]
+fn test_signals_decode_from_byte_ranges() {
+    //0x1234 little endian is 4660 mV, byte 2 is -5 degrees
+    let frame = ZanCanFrame::from_raw(0x0412, &[0x34, 0x12, 0xFB, 0x00]).expect("expected frame to be created");
+
+    assert_eq!(VOLTAGE.raw(&frame), Some(0x1234));
+    assert_eq!(TEMPERATURE.raw(&frame), Some(-5));
+
+    let signals = [VOLTAGE, TEMPERATURE];
+    let message = DbcMessage { id: 0x0412, signals: &signals };
+    let decoded: Vec<(&str, f64)> = message.decode(&frame).collect();
+    assert_eq!(decoded.len(), 2);
+    assert_eq!(decoded[0].0, "Voltage");
+    assert!((decoded[0].1 - 4.66).abs() < 1e-9);
+    assert_eq!(decoded[1], ("Temperature", -5.0));
+
+    assert_eq!(DbcMessage { id: 0x0413, signals: &signals }.decode(&frame).count(), 0);
+}
+
+#[test]
+fn test_motorola_signal_spans_bytes() {
+    let frame = ZanCanFrame::from_raw(0x0412, &[0x12, 0x34]).expect("expected frame to be created");
+    let word = Signal { name: "Word", start_bit: 7, length: 16, byte_order: ByteOrder::Motorola, signed: false, factor: 1.0, offset: 0.0 };
+
+    assert_eq!(word.raw(&frame), Some(0x1234));
+    assert_eq!(Signal { start_bit: 60, ..VOLTAGE }.raw(&frame), None);
+}
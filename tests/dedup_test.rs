@@ -0,0 +1,31 @@
+use zan_can::{address::ZanCanAddress, dedup::DedupFilter, error::ErrorCode, ZanCanFrame};
+
+#[test]
+fn test_dedup_catches_repeats_from_the_same_sender() {
+    let a = ZanCanAddress::from(0x01);
+    let b = ZanCanAddress::from(0x02);
+    let mut filter: DedupFilter<4> = DedupFilter::new();
+
+    assert!(!filter.is_duplicate(&ZanCanFrame::new_heartbeat(a, 1)));
+    assert!(filter.is_duplicate(&ZanCanFrame::new_heartbeat(a, 1)));
+    //same content from someone else, or a different type from the same sender, isn't a repeat
+    assert!(!filter.is_duplicate(&ZanCanFrame::new_heartbeat(b, 1)));
+    assert!(!filter.is_duplicate(&ZanCanFrame::new_error(a, ErrorCode::BusOff)));
+    assert!(filter.is_duplicate(&ZanCanFrame::new_heartbeat(a, 1)));
+
+    assert!(!filter.is_duplicate(&ZanCanFrame::new_heartbeat(a, 2)));
+    assert!(filter.is_duplicate(&ZanCanFrame::new_heartbeat(a, 2)));
+}
+
+#[test]
+fn test_dedup_forgets_the_oldest_sender_when_full() {
+    let mut filter: DedupFilter<2> = DedupFilter::new();
+    let frames = [1u8, 2, 3].map(|addr| ZanCanFrame::new_heartbeat(ZanCanAddress::from(addr), 0));
+
+    for frame in &frames {
+        assert!(!filter.is_duplicate(frame));
+    }
+
+    assert!(filter.is_duplicate(&frames[2]));
+    assert!(!filter.is_duplicate(&frames[0]));
+}
@@ -1,11 +1,14 @@
-use zan_can::{address::ZanCanAddress, emegency::{EmegencyStatus, EmergencyReason}, ZanCanFrame};
-use embedded_can::Frame;
+mod common;
+
+use zan_can::{address::ZanCanAddress, emergency::{EmergencyStatus, EmergencyReason, EmergencySeverity, Subsystem}, error::{ErrorCode, ZanCanError}, ZanCanFrame};
+use embedded_can::{Frame, StandardId};
+use common::DriverFrame;
 
 
 #[test]
-fn test_emegency_encodes_then_decodes_to_same() {
+fn test_emergency_encodes_then_decodes_to_same() {
     let addr = ZanCanAddress::from(0x44);
-    let status = EmegencyStatus::Stop;
+    let status = EmergencyStatus::Stop;
     let reason = EmergencyReason::try_from(0x5123).expect("error generating reason");
 
     let frame = ZanCanFrame::new_emergency(addr, status, reason);
@@ -15,4 +18,147 @@ fn test_emegency_encodes_then_decodes_to_same() {
 
     assert_eq!(status, back_status);
     assert_eq!(reason, back_reason);
-}
\ No newline at end of file
+}
+#[test]
+fn test_emergency_debug_shows_decoded_payload() {
+    let reason = EmergencyReason::try_from(0x0123).expect("error generating reason");
+    let frame = ZanCanFrame::new_emergency(ZanCanAddress::from(0x12), EmergencyStatus::Clear, reason);
+
+    let out = format!("{:?}", frame);
+
+    assert!(out.contains("address: 0x12"));
+    assert!(out.contains("status: Clear"));
+    assert!(out.contains("reason: 291"));
+}
+
+#[test]
+fn test_every_status_and_reason_round_trips() {
+    let addr = ZanCanAddress::from(0x01);
+    for status in [EmergencyStatus::Stop, EmergencyStatus::Clear] {
+        for raw_reason in 0..0x8000u16 {
+            let reason = EmergencyReason::try_from(raw_reason).expect("error generating reason");
+            let frame = ZanCanFrame::new_emergency(addr, status, reason);
+
+            assert_eq!(frame.decode_emergency(), Ok((status, reason)));
+        }
+    }
+}
+
+#[test]
+fn test_known_reasons_have_names() {
+    for reason in EmergencyReason::all() {
+        assert!(reason.is_known());
+        assert_eq!(EmergencyReason::try_from(u16::from(reason)), Ok(reason));
+    }
+
+    assert_eq!(EmergencyReason::OVER_TEMPERATURE.name(), "OverTemperature");
+    assert!(!EmergencyReason::try_from(0x7FFF).expect("error generating reason").is_known());
+}
+
+//Only compiles if decode errors don't borrow from the frame they came from
+fn decode_from_temporary_frame() -> Result<(EmergencyStatus, EmergencyReason), zan_can::error::ZanCanError> {
+    let frame = ZanCanFrame::new_heartbeat(ZanCanAddress::from(0x01), 0);
+    frame.decode_emergency()
+}
+
+#[test]
+fn test_decode_emergency_error_outlives_frame() {
+    assert!(decode_from_temporary_frame().is_err());
+}
+
+#[test]
+fn test_short_emergency_frame_is_rejected() {
+    let id = StandardId::new(0x0012).expect("error generating id");
+    let frame = ZanCanFrame::from_frame_truncating(DriverFrame::new(id, &[0x80]).expect("error generating driver frame")).expect("frame id holds a known type");
+
+    assert_eq!(frame.decode_emergency(), Err(zan_can::error::ZanCanError::TruncatedData));
+}
+
+#[test]
+fn test_emergency_display_is_one_line() {
+    let frame = ZanCanFrame::new_emergency(ZanCanAddress::from(0x12), EmergencyStatus::Stop, EmergencyReason::OVER_TEMPERATURE);
+
+    assert_eq!(frame.to_string(), "EMERGENCY node=0x12 status=Stop reason=OverTemperature");
+}
+
+#[test]
+fn test_reason_severity() {
+    assert_eq!(EmergencyReason::OVER_TEMPERATURE.severity(), EmergencySeverity::Warning);
+    assert_eq!(EmergencyReason::OBSTRUCTION.severity(), EmergencySeverity::Critical);
+    assert_eq!(EmergencyReason::try_from(0x7FFF).expect("error generating reason").severity(), EmergencySeverity::Critical);
+    assert!(EmergencySeverity::Info < EmergencySeverity::Warning && EmergencySeverity::Warning < EmergencySeverity::Critical);
+}
+
+#[test]
+fn test_status_reports_active_and_cleared() {
+    let addr = ZanCanAddress::from(0x12);
+    let stop = ZanCanFrame::new_emergency(addr, EmergencyStatus::Stop, EmergencyReason::OBSTRUCTION).decode_emergency().expect("expected to decode emergency").0;
+    let clear = ZanCanFrame::new_emergency(addr, EmergencyStatus::Clear, EmergencyReason::OBSTRUCTION).decode_emergency().expect("expected to decode emergency").0;
+
+    assert!(stop.is_active() && !stop.is_cleared());
+    assert!(clear.is_cleared() && !clear.is_active());
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_misspelled_names_still_work() {
+    use zan_can::emegency::{self, EmegencyStatus};
+
+    let frame = ZanCanFrame::new_emergency(ZanCanAddress::from(0x12), EmegencyStatus::Clear, emegency::EmergencyReason::MANUAL_STOP);
+
+    assert_eq!(frame.decode_emergency().map(|(status, _)| status), Ok(EmergencyStatus::Clear));
+    assert_eq!(emegency::STATUS_BIT, zan_can::emergency::STATUS_BIT);
+}
+
+#[test]
+fn test_reasons_group_by_subsystem() {
+    assert_eq!(EmergencyReason::MANUAL_STOP.subsystem(), Subsystem::Operator);
+    assert_eq!(EmergencyReason::OVER_CURRENT.subsystem(), Subsystem::Power);
+    assert_eq!(EmergencyReason::OVER_TEMPERATURE.subsystem(), Subsystem::Thermal);
+    assert_eq!(EmergencyReason::HEARTBEAT_LOST.subsystem(), Subsystem::Communication);
+    assert_eq!(EmergencyReason::OBSTRUCTION.subsystem(), Subsystem::Mechanical);
+    assert_eq!(EmergencyReason::try_from(0x0207).map(|r| r.subsystem()), Ok(Subsystem::Thermal));
+    assert_eq!(EmergencyReason::try_from(0x7001).map(|r| r.subsystem()), Ok(Subsystem::Other));
+}
+
+#[test]
+fn test_error_codes_escalate_to_emergency_reasons() {
+    for code in ErrorCode::all() {
+        if let Ok(reason) = EmergencyReason::try_from(code) {
+            assert_eq!(ErrorCode::try_from(reason), Ok(code));
+        }
+    }
+
+    assert_eq!(EmergencyReason::try_from(ErrorCode::OverTemperature), Ok(EmergencyReason::OVER_TEMPERATURE));
+    assert_eq!(EmergencyReason::try_from(ErrorCode::SensorFault), Err(ZanCanError::NoEquivalent));
+    assert_eq!(ErrorCode::try_from(EmergencyReason::MANUAL_STOP), Err(ZanCanError::NoEquivalent));
+}
+
+#[test]
+fn test_hand_built_unknown_codes_escalate_by_value() {
+    let code = ErrorCode::Unknown(0x0201);
+
+    assert_eq!(code, ErrorCode::OverTemperature);
+    assert_eq!(EmergencyReason::try_from(code), Ok(EmergencyReason::OVER_TEMPERATURE));
+    assert_eq!(EmergencyReason::try_from(ErrorCode::Unknown(0x7F00)), Err(ZanCanError::NoEquivalent));
+}
+
+#[test]
+fn test_zero_length_emergency_is_truncated() {
+    let id = StandardId::new(0x0021).expect("error generating id");
+    let empty = ZanCanFrame::new(id, &[]).expect("empty frame is valid");
+    let remote = ZanCanFrame::new_remote(id, 0).expect("remote frame is valid");
+
+    assert_eq!(empty.decode_emergency(), Err(ZanCanError::TruncatedData));
+    assert_eq!(remote.decode_emergency(), Err(ZanCanError::TruncatedData));
+    assert!(empty.decode().is_err());
+}
+
+#[test]
+fn test_every_reason_has_a_recommended_action() {
+    for reason in EmergencyReason::all() {
+        assert!(!reason.recommended_action().is_empty(), "{} has no action", reason.name());
+    }
+    assert_eq!(EmergencyReason::OVER_TEMPERATURE.recommended_action(), "Check cooling fan and let the node cool down");
+    assert!(!EmergencyReason { reason: 0x7ABC }.recommended_action().is_empty());
+}
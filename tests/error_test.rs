@@ -1,4 +1,113 @@
+mod common;
 
+use zan_can::{address::ZanCanAddress, error::{ErrorCode, ZanCanError}, message::ZanCanMessage, zan_can_type::ZanCanFrameType, ZanCanFrame};
+use embedded_can::{Frame, StandardId};
+use common::DriverFrame;
+
+#[test]
 fn test_error_encodes_then_decodes_to_same() {
-    
-}
\ No newline at end of file
+    let addr = ZanCanAddress::from(0x21);
+    let code = ErrorCode::from(0xBEEF);
+
+    let frame = ZanCanFrame::new_error(addr, code);
+
+    assert_eq!(ZanCanAddress::try_from(frame.id()).expect("address failed to convert which it shouldn't have"), addr);
+    let back_code = frame.decode_error().expect("expected to be able to decode error frame but it failed");
+
+    assert_eq!(code, back_code);
+}
+
+#[test]
+fn test_decoding_wrong_frame_type_reports_both_types() {
+    let frame = ZanCanFrame::new_error(ZanCanAddress::from(0x21), ErrorCode::OverVoltage);
+
+    assert_eq!(frame.decode_emergency().err(), Some(ZanCanError::WrongFrameType { expected: ZanCanFrameType::Emergency, found: ZanCanFrameType::Error }));
+}
+
+#[test]
+fn test_known_codes_have_names_and_unknown_is_caught() {
+    for code in ErrorCode::all() {
+        assert!(code.is_known());
+        assert_ne!(code.name(), "Unknown");
+        assert_eq!(ErrorCode::from(u16::from(code)), code);
+    }
+
+    assert!(!ErrorCode::from(0xBEEF).is_known());
+    assert_eq!(ErrorCode::from(0x0201).name(), "OverTemperature");
+}
+
+#[test]
+fn test_unknown_error_code_survives_round_trip() {
+    let frame = ZanCanFrame::new_error(ZanCanAddress::from(0x21), ErrorCode::from(0x7E57));
+
+    let back_code = frame.decode_error().expect("expected to be able to decode error frame but it failed");
+
+    assert!(matches!(back_code, ErrorCode::Unknown(0x7E57)));
+    assert_eq!(u16::from(back_code), 0x7E57);
+}
+
+#[test]
+fn test_short_error_frame_is_rejected() {
+    let id = StandardId::new(0x0221).expect("error generating id");
+    let frame = ZanCanFrame::from_frame_truncating(DriverFrame::new(id, &[0x01]).expect("error generating driver frame")).expect("frame id holds a known type");
+
+    assert_eq!(frame.decode_error(), Err(ZanCanError::TruncatedData));
+}
+
+#[test]
+fn test_error_code_splits_into_category_and_detail() {
+    let code = ErrorCode::MessageTimeout;
+
+    assert_eq!(code.as_u16(), 0x0302);
+    assert_eq!(code.category(), 0x03);
+    assert_eq!(code.detail(), 0x02);
+    assert_eq!(ErrorCode::from(0xBEEF).category(), 0xBE);
+}
+
+#[test]
+fn test_errors_display_a_short_message() {
+    let err = ZanCanError::WrongFrameType { expected: ZanCanFrameType::Emergency, found: ZanCanFrameType::Error };
+
+    assert_eq!(format!("{}", err), "expected Emergency frame, found Error");
+    assert_eq!(format!("{}", ZanCanError::UnknownFrameType(0x0C)), "unknown frame type code 0xc");
+
+    let boxed: Box<dyn std::error::Error> = Box::new(ZanCanError::TruncatedData);
+    assert_eq!(boxed.to_string(), "frame data is too short");
+}
+
+#[test]
+fn test_error_cleared_round_trips() {
+    let addr = ZanCanAddress::from(0x21);
+
+    let raised = ZanCanFrame::new_error(addr, ErrorCode::OverCurrent);
+    let cleared = ZanCanFrame::new_error_cleared(addr, ErrorCode::OverCurrent);
+
+    assert_eq!(raised.decode_error_status(), Ok((ErrorCode::OverCurrent, false)));
+    assert_eq!(cleared.decode_error_status(), Ok((ErrorCode::OverCurrent, true)));
+    assert_eq!(cleared.decode_error(), Ok(ErrorCode::OverCurrent));
+    assert_eq!(ZanCanFrame::new_error_with_status(addr, ErrorCode::OverCurrent, false).decode_error_status(), Ok((ErrorCode::OverCurrent, false)));
+    assert!(matches!(cleared.decode(), Ok(ZanCanMessage::ErrorCleared(ErrorCode::OverCurrent))));
+    assert_eq!(cleared.to_string(), "ERROR node=0x21 code=OverCurrent cleared");
+}
+
+#[test]
+fn test_unknown_error_status_byte_is_rejected() {
+    let id = StandardId::new(0x0221).expect("error generating id");
+    let frame = ZanCanFrame::new(id, &[0x01, 0x03, 0x7F]).expect("frame fits");
+
+    assert_eq!(frame.decode_error_status(), Err(ZanCanError::InvalidErrorStatus));
+    assert!(frame.decode().is_err());
+    //The code itself still reads
+    assert_eq!(frame.decode_error(), Ok(ErrorCode::OverCurrent));
+}
+
+#[test]
+fn test_zero_length_error_is_truncated() {
+    let id = StandardId::new(0x0221).expect("error generating id");
+    let empty = ZanCanFrame::new(id, &[]).expect("empty frame is valid");
+    let remote = ZanCanFrame::new_remote(id, 2).expect("remote frame is valid");
+
+    assert_eq!(empty.decode_error(), Err(ZanCanError::TruncatedData));
+    assert_eq!(empty.decode_error_status(), Err(ZanCanError::TruncatedData));
+    assert_eq!(remote.decode_error(), Err(ZanCanError::TruncatedData));
+}
@@ -0,0 +1,74 @@
+use zan_can::{address::{ExtendedAddress, ZanCanAddress}, emergency::EmergencyReason, error::{ErrorCode, ZanCanError}, escalation::EscalationPolicy, ZanCanFrame};
+
+#[test]
+fn test_repeated_errors_escalate_once() {
+    let addr = ZanCanAddress::from(0x21);
+    let mut policy: EscalationPolicy<4> = EscalationPolicy::new(3, 1000);
+    let error = ZanCanFrame::new_error(addr, ErrorCode::OverTemperature);
+
+    policy.record(&error, 0).expect("should record");
+    policy.record(&error, 100).expect("should record");
+    assert_eq!(policy.poll(), None);
+
+    policy.record(&error, 200).expect("should record");
+    assert_eq!(policy.poll(), Some((addr, EmergencyReason::OVER_TEMPERATURE)));
+    assert_eq!(policy.poll(), None);
+}
+
+#[test]
+fn test_slow_cleared_or_unmapped_errors_do_not_escalate() {
+    let addr = ZanCanAddress::from(0x21);
+    let mut policy: EscalationPolicy<4> = EscalationPolicy::new(2, 1000);
+
+    //too far apart
+    policy.record(&ZanCanFrame::new_error(addr, ErrorCode::BusOff), 0).expect("should record");
+    policy.record(&ZanCanFrame::new_error(addr, ErrorCode::BusOff), 5000).expect("should record");
+    assert_eq!(policy.poll(), None);
+
+    //cleared in between
+    policy.record(&ZanCanFrame::new_error_cleared(addr, ErrorCode::BusOff), 5100).expect("should record");
+    policy.record(&ZanCanFrame::new_error(addr, ErrorCode::BusOff), 5200).expect("should record");
+    assert_eq!(policy.poll(), None);
+
+    //no emergency equivalent
+    policy.record(&ZanCanFrame::new_error(addr, ErrorCode::SensorFault), 0).expect("should record");
+    policy.record(&ZanCanFrame::new_error(addr, ErrorCode::SensorFault), 1).expect("should record");
+    assert_eq!(policy.poll(), None);
+}
+
+#[test]
+fn test_expired_counts_make_room_for_new_nodes() {
+    let mut policy: EscalationPolicy<2> = EscalationPolicy::new(2, 1000);
+    policy.record(&ZanCanFrame::new_error(ZanCanAddress::from(0x21), ErrorCode::BusOff), 0).expect("should record");
+    policy.record(&ZanCanFrame::new_error(ZanCanAddress::from(0x22), ErrorCode::BusOff), 0).expect("should record");
+
+    let late = ZanCanAddress::from(0x23);
+    policy.record(&ZanCanFrame::new_error(late, ErrorCode::BusOff), 1_000_000).expect("expired slot should be reused");
+    policy.record(&ZanCanFrame::new_error(late, ErrorCode::BusOff), 1_000_100).expect("should record");
+    assert_eq!(policy.poll(), Some((late, EmergencyReason::BUS_OFF)));
+}
+
+#[test]
+fn test_pending_escalations_keep_their_slot() {
+    let addr = ZanCanAddress::from(0x21);
+    let mut policy: EscalationPolicy<1> = EscalationPolicy::new(1, 1000);
+    policy.record(&ZanCanFrame::new_error(addr, ErrorCode::BusOff), 0).expect("should record");
+
+    let other = ZanCanFrame::new_error(ZanCanAddress::from(0x22), ErrorCode::BusOff);
+    assert_eq!(policy.record(&other, 1_000_000), Err(ZanCanError::BufferTooSmall));
+    assert_eq!(policy.poll(), Some((addr, EmergencyReason::BUS_OFF)));
+    policy.record(&other, 1_000_000).expect("polled slot should be reused");
+}
+
+#[test]
+fn test_extended_nodes_are_not_counted_under_their_low_byte() {
+    let addr = ZanCanAddress::from(0x01);
+    let mut policy: EscalationPolicy<4> = EscalationPolicy::new(2, 1000);
+    let error = ZanCanFrame::new_error(addr, ErrorCode::BusOff);
+
+    policy.record(&error, 0).expect("should record");
+    assert_eq!(policy.record(&error.with_extended_address(ExtendedAddress::from_const(0x0101)), 100), Err(ZanCanError::ExtendedIdNotSupported));
+    assert_eq!(policy.poll(), None);
+    policy.record(&error.with_extended_address(ExtendedAddress::from(addr)), 200).expect("address fits");
+    assert_eq!(policy.poll(), Some((addr, EmergencyReason::BUS_OFF)));
+}
@@ -0,0 +1,37 @@
+use zan_can::{address::{ExtendedAddress, ZanCanAddress, MAX_EXTENDED_ADDRESS}, emergency::{EmergencyStatus, EmergencyReason}, zan_can_type::ZanCanFrameType, ZanCanFrame};
+use embedded_can::{ExtendedId, Frame, Id};
+
+#[test]
+fn test_extended_frame_keeps_type_and_wide_address() {
+    let wide = ExtendedAddress::new(0x012_3456).expect("address should fit");
+    let frame = ZanCanFrame::new_heartbeat(ZanCanAddress::from(0x01), 1000).with_extended_address(wide);
+
+    assert!(frame.is_extended());
+    assert_eq!(frame.id(), Id::Extended(ExtendedId::new(0x1C12_3456).expect("error generating id")));
+    assert_eq!(frame.frame_type(), ZanCanFrameType::Heartbeat);
+    assert_eq!(ZanCanFrameType::try_from(frame.id()), Ok(ZanCanFrameType::Heartbeat));
+    assert_eq!(frame.extended_address(), wide);
+    assert_eq!(frame.address(), ZanCanAddress::from(0x56));
+    assert_eq!(frame.decode_heartbeat(), Ok(1000));
+}
+
+#[test]
+fn test_standard_frames_stay_the_default() {
+    let frame = ZanCanFrame::new_emergency(ZanCanAddress::from(0x12), EmergencyStatus::Stop, EmergencyReason::MANUAL_STOP);
+
+    assert!(!frame.is_extended());
+    assert_eq!(frame.extended_address(), ExtendedAddress::from(ZanCanAddress::from(0x12)));
+    assert!(!frame.with_address(ZanCanAddress::from(0x13)).is_extended());
+    assert!(ExtendedAddress::new(MAX_EXTENDED_ADDRESS + 1).is_none());
+}
+
+#[test]
+fn test_extended_frame_comes_back_from_raw() {
+    let frame = ZanCanFrame::from_raw_extended(0x1800_0042, &[0x01, 0x02, 0x03, 0x04]).expect("valid extended frame");
+
+    assert_eq!(frame.frame_type(), ZanCanFrameType::SetData);
+    assert_eq!(u32::from(frame.extended_address()), 0x42);
+    assert_eq!(ZanCanAddress::try_from(frame.id()), Ok(ZanCanAddress::from(0x42)));
+    assert!(ZanCanFrame::from_raw_extended(0x2000_0000, &[]).is_err());
+    assert!(frame.with_address(ZanCanAddress::from(0x07)).is_extended());
+}
@@ -0,0 +1,359 @@
+mod common;
+
+use zan_can::{address::{ExtendedAddress, ZanCanAddress}, error::{ErrorCode, ZanCanError}, message::ZanCanMessage, message_data::DataIdentifier, zan_can_type::ZanCanFrameType, ZanCanFrame};
+use embedded_can::{ExtendedId, Frame, StandardId};
+use common::DriverFrame;
+use std::collections::HashSet;
+
+#[test]
+fn test_new_builds_frame_from_id_and_data() {
+    let id = StandardId::new(0x0444).expect("error generating id");
+    let frame = ZanCanFrame::new(id, &[0x01, 0x02, 0x03]).expect("expected frame to be created");
+
+    assert_eq!(frame.frame_type(), ZanCanFrameType::SentData);
+    assert_eq!(frame.dlc(), 3);
+    assert_eq!(frame.data(), &[0x01, 0x02, 0x03]);
+}
+
+#[test]
+fn test_new_rejects_oversized_data() {
+    let id = StandardId::new(0x0444).expect("error generating id");
+
+    assert!(ZanCanFrame::new(id, &[0u8; 9]).is_none());
+}
+
+#[test]
+fn test_new_accepts_every_type_code_for_both_id_widths() {
+    //Every 3 bit type code is in use so an id of either width always holds a known type
+    let standard = StandardId::new(0x0144).expect("error generating id");
+    let extended = ExtendedId::new(0x0400_0044).expect("error generating id");
+
+    assert_eq!(ZanCanFrame::new(standard, &[]).map(|f| f.frame_type()), Some(ZanCanFrameType::DataNak));
+    assert_eq!(ZanCanFrame::new(extended, &[]).map(|f| f.frame_type()), Some(ZanCanFrameType::DataNak));
+}
+
+#[test]
+fn test_new_remote_builds_rtr_frame() {
+    let id = StandardId::new(0x0544).expect("error generating id");
+    let frame = ZanCanFrame::new_remote(id, 2).expect("expected remote frame to be created");
+
+    assert!(frame.is_remote_frame());
+    assert_eq!(frame.dlc(), 2);
+    assert!(frame.data().is_empty());
+    assert!(ZanCanFrame::new_remote(id, 9).is_none());
+}
+
+#[test]
+fn test_rtr_requests_decode_as_the_remote_default() {
+    let frame = ZanCanFrame::new_remote_request(ZanCanAddress::from(0x44), 4).expect("expected remote request to be created");
+
+    assert_eq!(frame.validate(), Ok(()));
+    assert_eq!(frame.decode(), Ok(ZanCanMessage::RequestData(DataIdentifier::REMOTE_DEFAULT)));
+    assert_eq!(frame.decode_request_data_with_args(), Ok((DataIdentifier::REMOTE_DEFAULT, &[] as &[u8])));
+    //Only RequestData has a meaning as a remote frame
+    let remote_heartbeat = ZanCanFrame::new_remote(StandardId::new(0x0744).expect("error generating id"), 4).expect("remote frame is valid");
+    assert_eq!(remote_heartbeat.validate(), Err(ZanCanError::TruncatedData));
+}
+
+#[test]
+fn test_rtr_frames_are_requests_and_survive_drivers() {
+    let frame = ZanCanFrame::new_remote_request(ZanCanAddress::from(0x44), 4).expect("expected remote request to be created");
+    assert!(frame.is_request());
+    assert!(!ZanCanFrame::new_heartbeat(ZanCanAddress::from(0x44), 0).is_request());
+
+    let driver: DriverFrame = frame.to_frame().expect("driver should take remote frames");
+    assert!(driver.is_remote_frame());
+    let back = ZanCanFrame::from_frame_truncating(driver).expect("frame id holds a known type");
+    assert_eq!(back, frame);
+    assert_eq!(back.frame_type(), ZanCanFrameType::RequestData);
+    assert_eq!(back.dlc(), 4);
+    //Application level requests are still the default
+    assert!(frame.is_valid());
+    assert!(!ZanCanFrame::new_request_data(ZanCanAddress::from(0x44), DataIdentifier::DEVICE_STATE).is_remote_frame());
+}
+
+#[test]
+fn test_from_frame_clamps_oversized_source() {
+    let id = StandardId::new(0x0444).expect("error generating id");
+    let mut payload = [0u8; 64];
+    payload[7] = 0x77;
+    payload[8] = 0x88;
+    let source = DriverFrame::new(id, &payload).expect("expected oversized frame to be created");
+
+    let frame = ZanCanFrame::from_frame_truncating(source).expect("frame id holds a known type");
+
+    assert_eq!(frame.dlc(), 8);
+    assert_eq!(frame.data()[7], 0x77);
+}
+
+#[test]
+fn test_copied_frame_is_equal_to_original() {
+    let id = StandardId::new(0x0444).expect("error generating id");
+    let frame = ZanCanFrame::new(id, &[0x01, 0x02]).expect("expected frame to be created");
+    let copy = frame;
+
+    assert!(frame == copy);
+}
+
+#[test]
+fn test_equality_ignores_bytes_past_dlc() {
+    let id = StandardId::new(0x0444).expect("error generating id");
+    let first = ZanCanFrame::from_frame_truncating(DriverFrame::new(id, &[0x01, 0x02, 0xAA, 0xAA]).expect("error generating driver frame").with_dlc(2)).expect("frame id holds a known type");
+    let second = ZanCanFrame::from_frame_truncating(DriverFrame::new(id, &[0x01, 0x02, 0xBB]).expect("error generating driver frame").with_dlc(2)).expect("frame id holds a known type");
+
+    let mut set = HashSet::new();
+    set.insert(first);
+
+    assert_eq!(first, second);
+    assert!(set.contains(&second));
+}
+
+#[test]
+fn test_address_is_recovered_from_received_frame() {
+    let sent = ZanCanFrame::new_error(ZanCanAddress::from(0xA7), ErrorCode::from(0x0001));
+    let received = ZanCanFrame::from_frame_truncating(sent).expect("frame id holds a known type");
+
+    assert_eq!(received.address(), ZanCanAddress::from(0xA7));
+}
+
+#[test]
+fn test_id_from_type_and_address_packs_type_above_address() {
+    let id = zan_can::id_from_type_and_address(ZanCanFrameType::SetData, ZanCanAddress::from(0xFF)).expect("expected id to fit");
+
+    assert_eq!(id, embedded_can::Id::Standard(StandardId::new(0x06FF).expect("error generating id")));
+}
+
+#[test]
+fn test_type_code_and_address_bits_split_the_id() {
+    let id = StandardId::new(0x05AB).expect("error generating id");
+    let frame = ZanCanFrame::new(id, &[0x01]).expect("expected frame to be created");
+
+    assert_eq!(frame.type_code(), u8::from(ZanCanFrameType::RequestData));
+    assert_eq!(frame.address_bits(), 0xAB);
+}
+
+#[test]
+fn test_to_frame_converts_into_driver_frame() {
+    let frame = ZanCanFrame::new_heartbeat(ZanCanAddress::from(0x42), 0x0102_0304);
+
+    let driver: DriverFrame = frame.to_frame().expect("expected driver frame to be built");
+
+    assert_eq!(driver.id(), frame.id());
+    assert_eq!(driver.data(), &[0x01, 0x02, 0x03, 0x04]);
+}
+
+#[test]
+fn test_with_address_only_changes_address() {
+    let id = StandardId::new(0x0411).expect("error generating id");
+    let frame = ZanCanFrame::new(id, &[0x81, 0x02, 0x03]).expect("expected frame to be created");
+
+    let forwarded = frame.with_address(ZanCanAddress::from(0x99));
+
+    assert_eq!(forwarded.address(), ZanCanAddress::from(0x99));
+    assert_eq!(forwarded.frame_type(), ZanCanFrameType::SentData);
+    assert_eq!(forwarded.data(), frame.data());
+}
+
+#[test]
+fn test_from_raw_validates_untrusted_input() {
+    let frame = ZanCanFrame::from_raw(0x0712, &[0x00, 0x00, 0x00, 0x05]).expect("expected frame to be created");
+
+    assert_eq!(frame.decode_heartbeat(), Ok(5));
+    assert_eq!(ZanCanFrame::from_raw(0x0800, &[]), Err(ZanCanError::InvalidId));
+    assert_eq!(ZanCanFrame::from_raw(0x0712, &[0u8; 9]), Err(ZanCanError::OversizedData));
+}
+
+#[test]
+fn test_parts_round_trip() {
+    let frame = ZanCanFrame::new_heartbeat(ZanCanAddress::from(0x42), 77);
+
+    let (id, data, data_len, f_type, remote_dlc) = frame.into_parts();
+
+    assert_eq!(ZanCanFrame::from_parts(id, data, data_len, f_type, remote_dlc), frame);
+}
+
+#[test]
+fn test_rtr_requests_survive_parts_and_bytes() {
+    let frame = ZanCanFrame::new_remote_request(ZanCanAddress::from(0x44), 4).expect("dlc fits");
+
+    let (id, data, data_len, f_type, remote_dlc) = frame.into_parts();
+    assert_eq!(remote_dlc, Some(4));
+    let back = ZanCanFrame::from_parts(id, data, data_len, f_type, remote_dlc);
+    assert_eq!(back, frame);
+    assert!(back.is_remote_frame());
+
+    let bytes = frame.to_bytes().expect("standard frame should pack");
+    assert_eq!(bytes, [0xA5, 0x44, 0, 0, 0, 0, 0, 0, 0, 0]);
+    let back = ZanCanFrame::from_bytes(&bytes).expect("rtr frame should unpack");
+    assert_eq!(back, frame);
+    assert_eq!(back.dlc(), 4);
+    assert_eq!(back.decode_request_data(), Ok(DataIdentifier::REMOTE_DEFAULT));
+}
+
+#[test]
+fn test_raw_id_for_both_id_widths() {
+    let frame = ZanCanFrame::new_heartbeat(ZanCanAddress::from(0x42), 0);
+
+    assert_eq!(frame.raw_id(), 0x0742);
+    assert_eq!(frame.with_extended_address(ExtendedAddress::from_const(0x12_3456)).raw_id(), 0x1C12_3456);
+}
+
+#[test]
+fn test_write_hex_renders_significant_bytes() {
+    let frame = ZanCanFrame::from_raw(0x0742, &[0x00, 0x0A, 0xFF]).expect("expected frame to be created");
+    let mut buf = [0u8; 16];
+
+    let len = frame.write_hex(&mut buf).expect("buffer is big enough");
+    assert_eq!(&buf[..len], b"00 0a ff");
+    assert_eq!(frame.write_hex(&mut buf[..7]), Err(ZanCanError::BufferTooSmall));
+    assert_eq!(ZanCanFrame::from_raw(0x0742, &[]).expect("expected frame to be created").write_hex(&mut []), Ok(0));
+}
+
+#[test]
+fn test_fixed_bytes_round_trip_and_validate() {
+    let frame = ZanCanFrame::new_heartbeat(ZanCanAddress::from(0x42), 0x0102_0304);
+
+    let bytes = frame.to_bytes().expect("standard frame should pack");
+    assert_eq!(bytes, [0x27, 0x42, 0x01, 0x02, 0x03, 0x04, 0x00, 0x00, 0x00, 0x00]);
+    assert_eq!(ZanCanFrame::from_bytes(&bytes), Ok(frame));
+
+    //dlc 9 in the top bits
+    assert_eq!(ZanCanFrame::from_bytes(&[0x4F, 0x42, 0, 0, 0, 0, 0, 0, 0, 0]), Err(ZanCanError::OversizedData));
+    assert_eq!(frame.with_extended_address(ExtendedAddress::from_const(0x42)).to_bytes(), Err(ZanCanError::ExtendedIdNotSupported));
+}
+
+#[test]
+fn test_fixed_bytes_round_trip_a_full_frame() {
+    let frame = ZanCanFrame::from_raw(0x0442, &[0xC1, 1, 2, 3, 4, 5, 6, 7]).expect("expected frame to be created");
+
+    let bytes = frame.to_bytes().expect("standard frame should pack");
+    assert_eq!(bytes, [0x44, 0x42, 0xC1, 1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(ZanCanFrame::from_bytes(&bytes), Ok(frame));
+}
+
+#[test]
+fn test_built_frames_are_valid() {
+    let addr = ZanCanAddress::from(0x42);
+
+    assert!(ZanCanFrame::new_heartbeat(addr, 1).is_valid());
+    assert!(ZanCanFrame::new_error(addr, ErrorCode::BusOff).is_valid());
+    assert_eq!(ZanCanFrame::from_raw(0x0242, &[0x01]).expect("expected frame to be created").validate(), Err(ZanCanError::TruncatedData));
+}
+
+#[test]
+fn test_raw_payload_for_frames_without_a_decoder() {
+    let time = ZanCanFrame::from_raw(0x0342, &[1, 2, 3, 4, 5, 6, 7, 8]).expect("expected frame to be created");
+    let truncated = ZanCanFrame::from_frame_truncating(DriverFrame::new(StandardId::new(0x0342).expect("error generating id"), &[9, 9, 9]).expect("error generating driver frame").with_dlc(1)).expect("frame id holds a known type");
+
+    assert_eq!(time.raw_payload(), &[1, 2, 3, 4, 5, 6, 7, 8]);
+    assert_eq!(truncated.raw_payload(), &[9]);
+}
+
+#[test]
+fn test_data_len_never_passes_eight() {
+    let id = StandardId::new(0x0342).expect("error generating id");
+    let from_driver = ZanCanFrame::from_frame_truncating(DriverFrame::new(id, &[0xAA; 12]).expect("error generating driver frame").with_dlc(15)).expect("frame id holds a known type");
+    let from_parts = ZanCanFrame::from_parts(embedded_can::Id::Standard(id), [0xBB; 8], 40, ZanCanFrameType::Time, None);
+
+    for frame in [from_driver, from_parts] {
+        assert_eq!(frame.dlc(), 8);
+        assert_eq!(frame.significant_len(), 8);
+        assert_eq!(frame.data().len(), 8);
+    }
+}
+
+#[test]
+fn test_wire_view_gives_id_and_significant_data() {
+    let frame = ZanCanFrame::from_raw(0x0742, &[0x00, 0x0A, 0xFF]).expect("expected frame to be created");
+
+    assert_eq!(frame.wire_view(), (0x0742, &[0x00, 0x0A, 0xFF][..]));
+    assert_eq!(ZanCanFrame::new_remote_request(ZanCanAddress::from(0x42), 4).expect("dlc fits").wire_view().1, &[] as &[u8]);
+}
+
+#[test]
+fn test_parse_prefix_walks_a_byte_stream() {
+    let first = ZanCanFrame::new_heartbeat(ZanCanAddress::from(0x42), 7);
+    let second = ZanCanFrame::new_error(ZanCanAddress::from(0x21), ErrorCode::BusOff);
+    let mut stream = Vec::new();
+    stream.extend_from_slice(&first.to_bytes().expect("standard id fits"));
+    stream.extend_from_slice(&second.to_bytes().expect("standard id fits"));
+    stream.extend_from_slice(&[0x07, 0x42]);
+
+    let (frame, rest) = ZanCanFrame::parse_prefix(&stream).expect("first frame is complete");
+    assert_eq!(frame, first);
+    let (frame, rest) = ZanCanFrame::parse_prefix(rest).expect("second frame is complete");
+    assert_eq!(frame, second);
+    assert_eq!(ZanCanFrame::parse_prefix(rest).err(), Some(ZanCanError::NeedMoreData));
+}
+
+#[test]
+fn test_parse_prefix_takes_full_frames_and_rejects_crafted_ones() {
+    let full = ZanCanFrame::from_raw(0x0442, &[0xC1, 1, 2, 3, 4, 5, 6, 7]).expect("expected frame to be created");
+    let mut stream = Vec::new();
+    stream.extend_from_slice(&full.to_bytes().expect("standard id fits"));
+    stream.extend_from_slice(&full.to_bytes().expect("standard id fits"));
+
+    let (frame, rest) = ZanCanFrame::parse_prefix(&stream).expect("first frame is complete");
+    assert_eq!(frame, full);
+    let (frame, rest) = ZanCanFrame::parse_prefix(rest).expect("second frame is complete");
+    assert_eq!(frame, full);
+    assert!(rest.is_empty());
+
+    //Largest dlc the header can claim
+    assert_eq!(ZanCanFrame::parse_prefix(&[0xFF, 0xFF, 0, 0, 0, 0, 0, 0, 0, 0]).err(), Some(ZanCanError::OversizedData));
+}
+
+#[test]
+fn test_frames_can_hold_a_larger_buffer() {
+    let id = StandardId::new(0x0742).expect("error generating id");
+    let payload = [0xAB; 20];
+
+    let fd: ZanCanFrame<64> = Frame::new(id, &payload).expect("20 bytes fit in 64");
+    assert_eq!(fd.data(), &payload);
+    assert!(fd.is_valid());
+    assert!(<ZanCanFrame as Frame>::new(id, &payload).is_none());
+
+    let classic = ZanCanFrame::new_heartbeat(ZanCanAddress::from(0x42), 7);
+    let widened = classic.resize::<64>().expect("classic frame fits in 64");
+    assert_eq!(widened.decode_heartbeat(), Ok(7));
+    assert_eq!(widened.resize::<8>(), Some(classic));
+    assert_eq!(fd.resize::<8>(), None);
+}
+
+#[test]
+fn test_larger_frames_only_take_can_fd_lengths() {
+    let id = StandardId::new(0x0742).expect("error generating id");
+
+    for len in [0, 8, 12, 16, 20, 24, 32, 48, 64] {
+        assert!(<ZanCanFrame<64> as Frame>::new(id, &[0u8; 64][..len]).is_some(), "{} is a CAN-FD length", len);
+    }
+    for len in [9, 13, 33, 63] {
+        assert!(<ZanCanFrame<64> as Frame>::new(id, &[0u8; 64][..len]).is_none(), "{} is not a CAN-FD length", len);
+    }
+    assert!(!ZanCanFrame::<64>::is_valid_data_len(10));
+}
+
+#[test]
+fn test_from_frame_length_is_what_was_copied() {
+    let id = StandardId::new(0x0342).expect("error generating id");
+    let long_dlc = ZanCanFrame::from_frame_truncating(DriverFrame::new(id, &[1, 2, 3]).expect("error generating driver frame").with_dlc(8)).expect("frame id holds a known type");
+    let short_dlc = ZanCanFrame::from_frame_truncating(DriverFrame::new(id, &[1, 2, 3]).expect("error generating driver frame").with_dlc(2)).expect("frame id holds a known type");
+
+    assert_eq!(long_dlc.data(), &[1, 2, 3]);
+    assert_eq!(long_dlc.dlc(), 3);
+    assert_eq!(short_dlc.data(), &[1, 2]);
+}
+
+#[test]
+fn test_oversized_source_truncates_or_rejects() {
+    let id = StandardId::new(0x0342).expect("error generating id");
+    let source = || DriverFrame::new(id, &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]).expect("driver frames hold 64 bytes");
+
+    assert_eq!(ZanCanFrame::from_frame_truncating(source()).map(|f| f.data().len()), Ok(8));
+    assert_eq!(ZanCanFrame::try_from_frame(source()), Err(ZanCanError::OversizedData));
+    assert_eq!(ZanCanFrame::try_from_frame(DriverFrame::new(id, &[1, 2]).expect("error generating driver frame")).map(|f| f.data().len()), Ok(2));
+
+    #[allow(deprecated)]
+    let old = ZanCanFrame::from_frame(source());
+    assert_eq!(old.data(), &[1, 2, 3, 4, 5, 6, 7, 8]);
+}
@@ -0,0 +1,17 @@
+use zan_can::{address::ZanCanAddress, error::{ErrorCode, ZanCanError}, zan_can_type::ZanCanFrameType, ZanCanFrame};
+
+#[test]
+fn test_heartbeat_encodes_then_decodes_to_same() {
+    let frame = ZanCanFrame::new_heartbeat(ZanCanAddress::from(0x31), 0xDEAD_BEEF);
+
+    assert_eq!(frame.frame_type(), ZanCanFrameType::Heartbeat);
+    assert_eq!(frame.address(), ZanCanAddress::from(0x31));
+    assert_eq!(frame.decode_heartbeat(), Ok(0xDEAD_BEEF));
+}
+
+#[test]
+fn test_heartbeat_decode_rejects_other_types() {
+    let frame = ZanCanFrame::new_error(ZanCanAddress::from(0x31), ErrorCode::BusOff);
+
+    assert_eq!(frame.decode_heartbeat(), Err(ZanCanError::WrongFrameType { expected: ZanCanFrameType::Heartbeat, found: ZanCanFrameType::Error }));
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "json")]
+
+use zan_can::{address::ZanCanAddress, emergency::{EmergencyReason, EmergencyStatus}, error::ErrorCode, message_data::{DataIdentifier, DataMessage}, ZanCanFrame};
+
+fn json(frame: &ZanCanFrame) -> String {
+    let mut out = String::new();
+    frame.to_json(&mut out).expect("writing to a String can't fail");
+    out
+}
+
+#[test]
+fn test_json_decodes_by_frame_type() {
+    let addr = ZanCanAddress::from(0x12);
+
+    assert_eq!(json(&ZanCanFrame::new_emergency(addr, EmergencyStatus::Stop, EmergencyReason::OVER_TEMPERATURE)),
+        r#"{"type":"Emergency","addr":18,"status":"Stop","reason":"OverTemperature","reason_code":513}"#);
+    assert_eq!(json(&ZanCanFrame::new_error_cleared(addr, ErrorCode::BusOff)),
+        r#"{"type":"Error","addr":18,"code":"BusOff","code_value":769,"cleared":true}"#);
+    assert_eq!(json(&ZanCanFrame::new_heartbeat(addr, 42)), r#"{"type":"Heartbeat","addr":18,"uptime_ms":42}"#);
+
+    let message = DataMessage::new(DataIdentifier::DEVICE_STATE, &[0x01]).expect("fits");
+    assert_eq!(json(&ZanCanFrame::new_sent_data(addr, message)), r#"{"type":"SentData","addr":18,"identifier":4,"data":[1]}"#);
+}
+
+#[test]
+fn test_json_falls_back_to_raw_bytes() {
+    let time = ZanCanFrame::from_raw(0x0312, &[1, 2, 3, 4, 5, 6, 7, 8]).expect("valid frame");
+    let short_heartbeat = ZanCanFrame::from_raw(0x0712, &[9]).expect("valid frame");
+
+    assert_eq!(json(&time), r#"{"type":"Time","addr":18,"data":[1,2,3,4,5,6,7,8]}"#);
+    assert_eq!(json(&short_heartbeat), r#"{"type":"Heartbeat","addr":18,"data":[9]}"#);
+}
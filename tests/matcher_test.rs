@@ -0,0 +1,46 @@
+use zan_can::{address::{ExtendedAddress, ZanCanAddress}, emergency::{EmergencyReason, EmergencyStatus}, matcher::FrameMatcher, zan_can_type::ZanCanFrameType, ZanCanFrame};
+
+#[test]
+fn test_matcher_narrows_by_type_and_address() {
+    let addr = ZanCanAddress::from(0x12);
+    let emergency = ZanCanFrame::new_emergency(addr, EmergencyStatus::Stop, EmergencyReason::MANUAL_STOP);
+    let heartbeat = ZanCanFrame::new_heartbeat(addr, 0);
+
+    let matcher = FrameMatcher::new().frame_type(ZanCanFrameType::Emergency).address(addr);
+    assert!(matcher.matches(&emergency));
+    assert!(!matcher.matches(&heartbeat));
+    assert!(!matcher.matches(&emergency.with_address(ZanCanAddress::from(0x13))));
+
+    assert!(FrameMatcher::new().matches(&heartbeat));
+    assert!(FrameMatcher::new().address(addr).matches(&heartbeat));
+}
+
+#[test]
+fn test_matcher_needs_the_whole_extended_address() {
+    let addr = ZanCanAddress::from(0x12);
+    let wide = ZanCanFrame::new_heartbeat(addr, 0).with_extended_address(ExtendedAddress::from_const(0x0112));
+
+    assert!(!FrameMatcher::new().address(addr).matches(&wide));
+    assert!(FrameMatcher::new().address(addr).matches(&wide.with_extended_address(ExtendedAddress::from(addr))));
+}
+
+#[test]
+fn test_acceptance_filter_mask_layout() {
+    let addr = ZanCanAddress::from(0x12);
+
+    assert_eq!(FrameMatcher::new().to_acceptance_filter(), (0x0000, 0x0000));
+    assert_eq!(FrameMatcher::new().frame_type(ZanCanFrameType::SetData).to_acceptance_filter(), (0x0600, 0x0700));
+    assert_eq!(FrameMatcher::new().address(addr).to_acceptance_filter(), (0x0012, 0x00FF));
+    assert_eq!(FrameMatcher::new().frame_type(ZanCanFrameType::Emergency).address(addr).to_acceptance_filter(), (0x0012, 0x07FF));
+}
+
+#[test]
+fn test_acceptance_filter_agrees_with_matches() {
+    let matcher = FrameMatcher::new().frame_type(ZanCanFrameType::Heartbeat);
+    let (id, mask) = matcher.to_acceptance_filter();
+
+    for t in ZanCanFrameType::all() {
+        let frame = ZanCanFrame::from_raw((u8::from(t) as u16) << 8 | 0x34, &[0u8; 8]).expect("expected frame to be created");
+        assert_eq!(frame.raw_id() as u16 & mask == id & mask, matcher.matches(&frame));
+    }
+}
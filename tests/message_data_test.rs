@@ -0,0 +1,257 @@
+mod common;
+
+use zan_can::{address::ZanCanAddress, error::ZanCanError, message_data::{DataIdentifier, DataMessage, SetCommand}, zan_can_type::ZanCanFrameType, ZanCanFrame};
+use embedded_can::{Frame, StandardId};
+use common::DriverFrame;
+
+#[test]
+fn test_data_message_rejects_payload_that_cannot_be_encoded() {
+    let data_id = DataIdentifier::try_from(0x01u32).expect("error generating data identifier");
+
+    assert_eq!(DataMessage::new(data_id, &[]).err(), Some(ZanCanError::TruncatedData));
+    assert_eq!(DataMessage::new(data_id, &[0u8; 5]).err(), Some(ZanCanError::PayloadTooLarge));
+}
+
+#[test]
+fn test_largest_data_message_fits_in_frame() {
+    let data_id = DataIdentifier::try_from(0x3FFF_FFFFu32).expect("error generating data identifier");
+    let message = DataMessage::new(data_id, &[0x01, 0x02, 0x03, 0x04]).expect("error generating data message");
+
+    let frame = ZanCanFrame::new_sent_data(ZanCanAddress::from(0x05), message);
+    let back = DataMessage::try_from(frame.data()).expect("expected to be able to decode data message");
+
+    assert_eq!(frame.dlc(), 8);
+    assert_eq!(back.data_identifier(), data_id);
+    assert_eq!(back.data(), &[0x01, 0x02, 0x03, 0x04]);
+}
+
+#[test]
+fn test_try_new_set_data_builds_frame() {
+    let data_id = DataIdentifier::try_from(0x04u32).expect("error generating data identifier");
+    let message = DataMessage::new(data_id, &[0x01]).expect("error generating data message");
+
+    let frame = ZanCanFrame::try_new_set_data(ZanCanAddress::from(0x05), message).expect("expected set data frame to be built");
+
+    assert_eq!(frame.data(), &[0x04, 0x01]);
+}
+
+#[test]
+fn test_frame_exposes_identifier_and_payload_without_full_decode() {
+    let data_id = DataIdentifier::try_from(0x0123u32).expect("error generating data identifier");
+    let message = DataMessage::new(data_id, &[0xAA, 0xBB, 0xCC]).expect("error generating data message");
+
+    let frame = ZanCanFrame::new_sent_data(ZanCanAddress::from(0x05), message);
+
+    assert_eq!(frame.data_identifier(), Ok(data_id));
+    assert_eq!(frame.data_payload(), &[0xAA, 0xBB, 0xCC]);
+}
+
+#[test]
+fn test_layout_constants_fill_a_frame() {
+    let data_id = DataIdentifier::try_from(0x3FFF_FFFFu32).expect("error generating data identifier");
+
+    assert_eq!(data_id.len(), DataIdentifier::MAX_BYTE_LEN);
+    assert_eq!(DataIdentifier::MAX_BYTE_LEN + DataMessage::MAX_PAYLOAD_LEN, 8);
+    assert!(DataMessage::new(data_id, &[0u8; DataMessage::MAX_PAYLOAD_LEN]).is_ok());
+}
+
+#[test]
+fn test_data_frame_display_shows_identifier_and_hex_payload() {
+    let data_id = DataIdentifier::try_from(0x04u32).expect("error generating data identifier");
+    let message = DataMessage::new(data_id, &[0x0A, 0xFF]).expect("error generating data message");
+
+    let frame = ZanCanFrame::new_set_data(ZanCanAddress::from(0x05), message);
+
+    assert_eq!(frame.to_string(), "SET_DATA node=0x05 id=0x4 data=0a ff");
+}
+
+#[test]
+fn test_data_message_splits_identifier_from_payload() {
+    let data_id = DataIdentifier::try_from(0x0456u32).expect("error generating data identifier");
+    let message = DataMessage::new(data_id, &[0x10, 0x20, 0x30]).expect("error generating data message");
+
+    let mut buff = [0u8; 8];
+    let written = message.write(&mut buff).expect("expected message to be written");
+    let back = DataMessage::try_from(&buff[..written]).expect("expected message to be read back");
+
+    assert_eq!(back.identifier(), data_id);
+    assert_eq!(back.payload(), &[0x10, 0x20, 0x30]);
+}
+
+#[test]
+fn test_decode_ignores_bytes_past_dlc() {
+    let id = StandardId::new(0x0405).expect("error generating id");
+    let source = DriverFrame::new(id, &[0x04, 0xAA, 0xFF, 0xFF]).expect("error generating driver frame").with_dlc(2);
+
+    let message = ZanCanFrame::from_frame_truncating(source).expect("frame id holds a known type").decode_sent_data().expect("expected sent data to decode");
+
+    assert_eq!(message.data_identifier(), DataIdentifier::try_from(0x04u32).expect("error generating data identifier"));
+    assert_eq!(message.data(), &[0xAA]);
+}
+
+#[test]
+fn test_fixed_size_payload_matches_dynamic() {
+    let data_id = DataIdentifier::try_from(0x04u32).expect("error generating data identifier");
+
+    let fixed = DataMessage::with_payload(data_id, [0x12u8, 0x34]);
+    let dynamic = DataMessage::new(data_id, &[0x12, 0x34]).expect("error generating data message");
+
+    assert_eq!(ZanCanFrame::new_sent_data(ZanCanAddress::from(0x01), fixed), ZanCanFrame::new_sent_data(ZanCanAddress::from(0x01), dynamic));
+}
+
+#[test]
+fn test_request_batch_yields_one_frame_per_identifier() {
+    let ids = [
+        DataIdentifier::try_from(0x00u32).expect("error generating data identifier"),
+        DataIdentifier::try_from(0x01u32).expect("error generating data identifier"),
+        DataIdentifier::try_from(0x0400u32).expect("error generating data identifier")
+    ];
+
+    let frames: Vec<ZanCanFrame> = ZanCanFrame::new_request_data_batch(ZanCanAddress::from(0x05), &ids).collect();
+
+    assert_eq!(frames.len(), 3);
+    for (frame, data_id) in frames.iter().zip(ids) {
+        assert_eq!(frame.address(), ZanCanAddress::from(0x05));
+        assert_eq!(frame.decode_request_data(), Ok(data_id));
+    }
+}
+
+#[test]
+fn test_checksum_survives_the_bus_and_catches_corruption() {
+    let data_id = DataIdentifier::try_from(0x0400u32).expect("error generating data identifier");
+    let message = DataMessage::with_checksum(data_id, &[0x0A, 0x0B, 0x0C]).expect("three bytes plus checksum should fit");
+
+    let frame = ZanCanFrame::new_sent_data(ZanCanAddress::from(0x05), message);
+    let back = frame.decode_sent_data().expect("expected to decode sent data");
+    assert!(back.verify_checksum());
+    assert_eq!(back.checked_payload(), Ok(&[0x0A, 0x0B, 0x0C][..]));
+
+    let mut raw = [0u8; 8];
+    let len = frame.dlc();
+    raw[..len].copy_from_slice(frame.data());
+    raw[len - 2] ^= 0x01;
+    let corrupted = DataMessage::try_from(&raw[..len]).expect("corrupted bytes still parse");
+    assert!(!corrupted.verify_checksum());
+    assert_eq!(corrupted.checked_payload(), Err(ZanCanError::ChecksumMismatch));
+}
+
+#[test]
+fn test_checksum_takes_a_payload_byte() {
+    let data_id = DataIdentifier::try_from(0x01u32).expect("error generating data identifier");
+
+    assert_eq!(DataMessage::with_checksum(data_id, &[1, 2, 3, 4]).err(), Some(ZanCanError::PayloadTooLarge));
+    assert_eq!(DataMessage::with_checksum(data_id, &[]).map(|m| m.data_len()), Ok(1));
+}
+
+#[test]
+fn test_every_registered_identifier_has_a_name() {
+    for data_id in DataIdentifier::all() {
+        assert!(data_id.name().is_some());
+        assert_eq!(DataIdentifier::try_from(data_id.as_u32()), Ok(data_id));
+    }
+
+    assert_eq!(DataIdentifier::DEVICE_STATE.name(), Some("DeviceState"));
+    assert_eq!(DataIdentifier::try_from(0x0400u32).map(|d| d.name()), Ok(None));
+}
+
+#[test]
+fn test_set_command_round_trips_through_set_data() {
+    let data_id = DataIdentifier::try_from(0x10u32).expect("error generating data identifier");
+    let message = DataMessage::with_command(data_id, SetCommand::Toggle, &[0x05]).expect("command and value should fit");
+
+    let back = ZanCanFrame::new_set_data(ZanCanAddress::from(0x05), message).decode_set_data().expect("expected to decode set data");
+
+    assert_eq!(back.command(), Some(SetCommand::Toggle));
+    assert_eq!(back.command_value(), &[0x05]);
+    assert_eq!(DataMessage::with_command(data_id, SetCommand::Set, &[1, 2, 3, 4]).err(), Some(ZanCanError::PayloadTooLarge));
+    assert_eq!(DataMessage::new(data_id, &[0x00]).map(|m| m.command()), Ok(None));
+}
+
+#[test]
+fn test_endian_helpers_pick_the_byte_order() {
+    let data_id = DataIdentifier::try_from(0x10u32).expect("error generating data identifier");
+    let mut message = DataMessage::with_payload(data_id, [0u8]);
+
+    message.write_u16_be(0x1234);
+    assert_eq!(message.payload(), &[0x12, 0x34]);
+    assert_eq!(message.read_u16_be(), Ok(0x1234));
+    assert_eq!(message.read_u16_le(), Ok(0x3412));
+    assert_eq!(message.read_u32_be(), Err(ZanCanError::TruncatedData));
+
+    message.write_u32_le(0x0102_0304);
+    assert_eq!(message.payload(), &[0x04, 0x03, 0x02, 0x01]);
+    let back = ZanCanFrame::new_sent_data(ZanCanAddress::from(0x05), message).decode_sent_data().expect("expected to decode sent data");
+    assert_eq!(back.read_u32_le(), Ok(0x0102_0304));
+}
+
+#[test]
+fn test_typed_values_at_offsets() {
+    let data_id = DataIdentifier::try_from(0x10u32).expect("error generating data identifier");
+    let mut message = DataMessage::with_payload(data_id, [0x7Fu8]);
+
+    message.write_i16(1, -1200).expect("fits after the first byte");
+    assert_eq!(message.payload(), &[0x7F, 0xFB, 0x50]);
+    assert_eq!(message.read_i16(1), Ok(-1200));
+    assert_eq!(message.read_i16(2), Err(ZanCanError::TruncatedData));
+    assert_eq!(message.write_u32(1, 5), Err(ZanCanError::PayloadTooLarge));
+
+    message.write_u32(0, 3300).expect("fills the payload");
+    let back = ZanCanFrame::new_sent_data(ZanCanAddress::from(0x05), message).decode_sent_data().expect("expected to decode sent data");
+    assert_eq!(back.read_u32(0), Ok(3300));
+}
+
+#[test]
+fn test_typed_values_at_huge_offsets_are_refused() {
+    let data_id = DataIdentifier::try_from(0x10u32).expect("error generating data identifier");
+    let mut message = DataMessage::with_payload(data_id, [0x7Fu8]);
+
+    assert_eq!(message.write_u16(usize::MAX, 1), Err(ZanCanError::PayloadTooLarge));
+    assert_eq!(message.read_u16(usize::MAX), Err(ZanCanError::TruncatedData));
+    assert_eq!(message.payload(), &[0x7F]);
+}
+
+#[test]
+fn test_request_args_ride_after_the_identifier() {
+    let addr = ZanCanAddress::from(0x05);
+    let data_id = DataIdentifier::try_from(0x0400u32).expect("error generating data identifier");
+
+    let frame = ZanCanFrame::new_request_data_with_args(addr, data_id, &[0x07, 0x01]).expect("args should fit");
+    assert_eq!(frame.data(), &[0x84, 0x00, 0x07, 0x01]);
+    assert_eq!(frame.decode_request_data_with_args(), Ok((data_id, &[0x07, 0x01][..])));
+    assert_eq!(frame.decode_request_data(), Ok(data_id));
+
+    let plain = ZanCanFrame::new_request_data(addr, data_id);
+    assert_eq!(plain.decode_request_data_with_args(), Ok((data_id, &[][..])));
+    assert_eq!(ZanCanFrame::new_request_data_with_args(addr, data_id, &[1, 2, 3, 4]).err(), Some(ZanCanError::PayloadTooLarge));
+}
+
+#[test]
+fn test_request_args_are_not_part_of_the_identifier() {
+    let data_id = DataIdentifier::try_from(0x04u32).expect("error generating data identifier");
+    let frame = ZanCanFrame::new_request_data_with_args(ZanCanAddress::from(0x05), data_id, &[0x07]).expect("args should fit");
+
+    assert_eq!(frame.data_identifier(), Ok(data_id));
+    assert_eq!(frame.data_payload(), &[0x07]);
+}
+
+#[test]
+fn test_capability_advertises_supported_types_and_version() {
+    let addr = ZanCanAddress::from(0x05);
+    let supported = ZanCanFrameType::Heartbeat.mask_bit() | ZanCanFrameType::DataNak.mask_bit();
+
+    let frame = ZanCanFrame::new_capability(addr, supported, 2);
+    assert_eq!(frame.frame_type(), ZanCanFrameType::SentData);
+    assert_eq!(frame.decode_capability(), Ok((0b1000_0010, 2)));
+
+    let other = ZanCanFrame::new_sent_data(addr, DataMessage::new(DataIdentifier::DEVICE_STATE, &[0x01, 0x02]).expect("fits"));
+    assert_eq!(other.decode_capability(), Err(ZanCanError::InvalidDataIdentifier));
+}
+
+#[test]
+fn test_set_target_borrows_the_payload() {
+    let data_id = DataIdentifier::try_from(0x0400u32).expect("error generating data identifier");
+    let frame = ZanCanFrame::new_set_data(ZanCanAddress::from(0x05), DataMessage::new(data_id, &[0x01, 0x02, 0x03, 0x04]).expect("fits"));
+
+    assert_eq!(frame.decode_set_target(), Ok((data_id, &[0x01, 0x02, 0x03, 0x04][..])));
+    assert_eq!(ZanCanFrame::new_heartbeat(ZanCanAddress::from(0x05), 0).decode_set_target().err(), Some(ZanCanError::WrongFrameType { expected: ZanCanFrameType::SetData, found: ZanCanFrameType::Heartbeat }));
+}
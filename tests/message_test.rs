@@ -0,0 +1,123 @@
+use zan_can::{address::{ExtendedAddress, ZanCanAddress}, emergency::{EmergencyStatus, EmergencyReason}, error::{ErrorCode, ZanCanError}, message::{decode_iter, InspectedFrame, ZanCanMessage}, message_data::{DataIdentifier, NakReason}, ZanCanFrame};
+use embedded_can::Frame;
+
+#[test]
+fn test_decode_dispatches_on_frame_type() {
+    let addr = ZanCanAddress::from(0x10);
+    let reason = EmergencyReason::try_from(0x0042).expect("error generating reason");
+    let data_id = DataIdentifier::try_from(0x04u32).expect("error generating data identifier");
+
+    match ZanCanFrame::new_emergency(addr, EmergencyStatus::Stop, reason).decode() {
+        Ok(ZanCanMessage::Emergency(status, back_reason)) => {
+            assert_eq!(status, EmergencyStatus::Stop);
+            assert_eq!(back_reason, reason);
+        },
+        other => panic!("expected emergency message but got {:?}", other)
+    }
+
+    match ZanCanFrame::new_error(addr, ErrorCode::OverVoltage).decode() {
+        Ok(ZanCanMessage::Error(code)) => assert_eq!(code, ErrorCode::OverVoltage),
+        other => panic!("expected error message but got {:?}", other)
+    }
+
+    match ZanCanFrame::new_request_data(addr, data_id).decode() {
+        Ok(ZanCanMessage::RequestData(back_id)) => assert_eq!(back_id, data_id),
+        other => panic!("expected request data message but got {:?}", other)
+    }
+}
+
+#[test]
+fn test_decoded_message_encodes_back_to_same_frame() {
+    let addr = ZanCanAddress::from(0x10);
+    let data_id = DataIdentifier::try_from(0x0123u32).expect("error generating data identifier");
+    let message = zan_can::message_data::DataMessage::new(data_id, &[0x01, 0x02]).expect("error generating data message");
+    let frames = [
+        ZanCanFrame::new_emergency(addr, EmergencyStatus::Clear, EmergencyReason::BUS_OFF),
+        ZanCanFrame::new_error(addr, ErrorCode::SensorFault),
+        ZanCanFrame::new_sent_data(addr, message),
+        ZanCanFrame::new_request_data(addr, data_id),
+        ZanCanFrame::new_set_data(addr, message),
+        ZanCanFrame::new_heartbeat(addr, 1234)
+    ];
+
+    for frame in frames {
+        let decoded = frame.decode().expect("expected frame to decode");
+
+        assert_eq!(decoded.encode(addr), frame);
+    }
+}
+
+#[test]
+fn test_decode_iter_keeps_errors_per_frame() {
+    let addr = ZanCanAddress::from(0x10);
+    let frames = [
+        ZanCanFrame::new_heartbeat(addr, 5),
+        ZanCanFrame::from_raw(0x0310, &[0u8; 8]).expect("time frame should build"),
+        ZanCanFrame::new_error(addr, ErrorCode::BusOff)
+    ];
+
+    let results: Vec<_> = decode_iter(frames.into_iter()).collect();
+
+    assert_eq!(results.len(), 3);
+    assert!(matches!(results[0], Ok(ZanCanMessage::Heartbeat(5))));
+    assert!(matches!(results[1], Err(ZanCanError::UnsupportedFrameType(_))));
+    assert!(matches!(results[2], Ok(ZanCanMessage::Error(ErrorCode::BusOff))));
+}
+
+#[test]
+fn test_data_nak_round_trips_and_decodes() {
+    let addr = ZanCanAddress::from(0x10);
+    let data_id = DataIdentifier::try_from(0x0400u32).expect("error generating data identifier");
+
+    let frame = ZanCanFrame::new_data_nak(addr, data_id, NakReason::UnsupportedIdentifier);
+
+    assert_eq!(frame.data(), &[0x04, 0x00, 0x01]);
+    assert_eq!(frame.decode_data_nak(), Ok((data_id, NakReason::UnsupportedIdentifier)));
+    assert!(matches!(frame.decode(), Ok(ZanCanMessage::DataNak(d, NakReason::UnsupportedIdentifier)) if d == data_id));
+    assert_eq!(ZanCanMessage::DataNak(data_id, NakReason::from(0x7F)).encode(addr).decode_data_nak(), Ok((data_id, NakReason::Unknown(0x7F))));
+}
+
+#[test]
+fn test_data_nak_without_reason_is_truncated() {
+    let frame = ZanCanFrame::from_raw(0x0110, &[0x04]).expect("data nak frame should build");
+
+    assert_eq!(frame.decode_data_nak(), Err(ZanCanError::TruncatedData));
+    assert!(matches!(frame.decode_request_data(), Err(ZanCanError::WrongFrameType { .. })));
+}
+
+#[test]
+fn test_semantically_eq_compares_decoded_content() {
+    let addr = ZanCanAddress::from(0x10);
+    let built = ZanCanFrame::new_emergency(addr, EmergencyStatus::Stop, EmergencyReason::OBSTRUCTION);
+    //Trailing bytes past the emergency payload don't change what the frame means
+    let received = ZanCanFrame::from_raw(0x0010, &[0x04, 0x01, 0xAA]).expect("emergency frame should build");
+
+    assert_ne!(built, received);
+    assert!(built.semantically_eq(&received));
+    assert!(!built.semantically_eq(&built.with_address(ZanCanAddress::from(0x11))));
+    assert!(!built.semantically_eq(&ZanCanFrame::new_emergency(addr, EmergencyStatus::Clear, EmergencyReason::OBSTRUCTION)));
+    assert!(!built.semantically_eq(&ZanCanFrame::new_heartbeat(addr, 0)));
+}
+
+#[test]
+fn test_inspect_keeps_the_decoded_message() {
+    let frame = ZanCanFrame::new_heartbeat(ZanCanAddress::from(0x10), 9);
+    let inspected = frame.inspect();
+
+    assert!(matches!(inspected.message(), Ok(ZanCanMessage::Heartbeat(9))));
+    assert_eq!(inspected.frame(), &frame);
+
+    let time = InspectedFrame::new(ZanCanFrame::from_raw(0x0310, &[0u8; 8]).expect("time frame should build"));
+    assert!(matches!(time.message(), Err(ZanCanError::UnsupportedFrameType(_))));
+}
+
+#[test]
+fn test_decode_with_address_pairs_sender_and_message() {
+    let addr = ZanCanAddress::from(0x12);
+    let frame = ZanCanFrame::new_emergency(addr, EmergencyStatus::Stop, EmergencyReason::MANUAL_STOP);
+
+    assert_eq!(frame.decode_with_address(), Ok((addr, ZanCanMessage::Emergency(EmergencyStatus::Stop, EmergencyReason::MANUAL_STOP))));
+
+    let wide = ZanCanFrame::new_heartbeat(addr, 5).with_extended_address(ExtendedAddress::from_const(0x12_3456));
+    assert_eq!(wide.decode_with_address(), Err(ZanCanError::ExtendedIdNotSupported));
+}
@@ -0,0 +1,85 @@
+use zan_can::{address::{ExtendedAddress, ZanCanAddress}, emergency::{EmergencyReason, EmergencyStatus}, message_data::{DataIdentifier, DataMessage, NakReason}, node::Node, zan_can_type::ZanCanFrameType, ZanCanFrame};
+use embedded_can::Frame;
+use std::cell::Cell;
+
+#[test]
+fn test_node_answers_a_scripted_sequence() {
+    let me = ZanCanAddress::from(0x20);
+    let state = Cell::new(0u8);
+    let mut node = Node::new(
+        me,
+        |data_id| match data_id {
+            DataIdentifier::DEVICE_STATE => Ok(DataMessage::with_payload(data_id, [state.get()])),
+            _ => Err(NakReason::UnsupportedIdentifier)
+        },
+        |message| match message.data_identifier() {
+            DataIdentifier::DEVICE_STATE => {
+                state.set(message.payload()[0]);
+                Ok(())
+            },
+            _ => Err(NakReason::AccessDenied)
+        }
+    );
+
+    let set = ZanCanFrame::new_set_data(me, DataMessage::with_payload(DataIdentifier::DEVICE_STATE, [0x01]));
+    assert_eq!(node.handle(&set), None);
+
+    let reply = node.handle(&ZanCanFrame::new_request_data(me, DataIdentifier::DEVICE_STATE)).expect("request should be answered");
+    let message = reply.decode_sent_data().expect("reply should be sent data");
+    assert_eq!(reply.address(), me);
+    assert_eq!(message.payload(), &[0x01]);
+
+    let nak = node.handle(&ZanCanFrame::new_request_data(me, DataIdentifier::DEVICE_MODEL)).expect("unsupported request should be refused");
+    assert_eq!(nak.decode_data_nak(), Ok((DataIdentifier::DEVICE_MODEL, NakReason::UnsupportedIdentifier)));
+
+    let refused = node.handle(&ZanCanFrame::new_set_data(me, DataMessage::with_payload(DataIdentifier::DEVICE_MODEL, [0x09])));
+    assert_eq!(refused.map(|f| f.frame_type()), Some(ZanCanFrameType::DataNak));
+}
+
+#[test]
+fn test_node_ignores_frames_for_other_nodes() {
+    let mut node = Node::new(ZanCanAddress::from(0x20), |d| Ok(DataMessage::with_payload(d, [0u8])), |_| Ok(()));
+
+    assert_eq!(node.handle(&ZanCanFrame::new_request_data(ZanCanAddress::from(0x21), DataIdentifier::DEVICE_STATE)), None);
+    assert!(node.handle(&ZanCanFrame::new_request_data(ZanCanAddress::BROADCAST, DataIdentifier::DEVICE_STATE)).is_some());
+    assert_eq!(node.handle(&ZanCanFrame::new_heartbeat(ZanCanAddress::from(0x20), 0)), None);
+    assert_eq!(node.emergency(EmergencyStatus::Stop, EmergencyReason::MANUAL_STOP).address(), node.address());
+}
+
+#[test]
+fn test_node_answers_rtr_requests_with_the_remote_default() {
+    let me = ZanCanAddress::from(0x20);
+    let mut node = Node::new(
+        me,
+        |data_id| match data_id {
+            DataIdentifier::REMOTE_DEFAULT => Ok(DataMessage::with_payload(data_id, [0x01])),
+            _ => Err(NakReason::UnsupportedIdentifier)
+        },
+        |_| Ok(())
+    );
+
+    let request = ZanCanFrame::new_remote_request(me, 2).expect("dlc fits");
+    let reply = node.handle(&request).expect("rtr request should be answered");
+    let message = reply.decode_sent_data().expect("reply should be sent data");
+    assert_eq!(message.data_identifier(), DataIdentifier::REMOTE_DEFAULT);
+    assert_eq!(message.payload(), &[0x01]);
+}
+
+#[test]
+fn test_node_matches_and_answers_extended_frames_by_the_whole_address() {
+    let me = ZanCanAddress::from(0x20);
+    let mut node = Node::new(me, |d| Ok(DataMessage::with_payload(d, [0u8])), |_| Ok(()));
+    let request = ZanCanFrame::new_request_data(me, DataIdentifier::DEVICE_STATE);
+
+    //Same low byte, different node
+    assert_eq!(node.handle(&request.with_extended_address(ExtendedAddress::from_const(0x0120))), None);
+
+    let reply = node.handle(&request.with_extended_address(ExtendedAddress::from(me))).expect("request should be answered");
+    assert!(reply.is_extended());
+    assert_eq!(reply.extended_address(), ExtendedAddress::from(me));
+    assert_eq!(reply.frame_type(), ZanCanFrameType::SentData);
+
+    let broadcast = request.with_extended_address(ExtendedAddress::BROADCAST);
+    assert!(node.handle(&broadcast).is_some_and(|r| r.is_extended()));
+    assert!(!node.handle(&request).is_some_and(|r| r.is_extended()));
+}
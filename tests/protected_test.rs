@@ -0,0 +1,38 @@
+use zan_can::{address::ZanCanAddress, error::ZanCanError, message_data::{DataIdentifier, DataMessage}, protected::ProtectedFrame, ZanCanFrame};
+use embedded_can::Frame;
+
+fn set_command() -> ZanCanFrame {
+    let data_id = DataIdentifier::try_from(0x0400u32).expect("error generating data identifier");
+    ZanCanFrame::new_set_data(ZanCanAddress::from(0x05), DataMessage::new(data_id, &[0x01, 0x02]).expect("fits"))
+}
+
+#[test]
+fn test_protected_frame_round_trips() {
+    let frame = set_command();
+    let protected = ProtectedFrame::new(&frame).expect("4 bytes leave room for the crc");
+
+    assert_eq!(protected.frame().data().len(), frame.data().len() + 2);
+    assert_eq!(ProtectedFrame::verify(protected.frame()), Ok(frame));
+    assert_eq!(ProtectedFrame::new(&ZanCanFrame::from_raw(0x0342, &[0; 7]).expect("valid frame")).err(), Some(ZanCanError::PayloadTooLarge));
+}
+
+#[test]
+fn test_flipped_bit_in_data_or_address_is_caught() {
+    let protected = *ProtectedFrame::new(&set_command()).expect("fits").frame();
+
+    let mut data = [0u8; 8];
+    data[..protected.data().len()].copy_from_slice(protected.data());
+    data[1] ^= 0x10;
+    let corrupted = ZanCanFrame::new(protected.id(), &data[..protected.data().len()]).expect("valid frame");
+    assert_eq!(ProtectedFrame::verify(&corrupted), Err(ZanCanError::ChecksumMismatch));
+
+    let misrouted = protected.with_address(ZanCanAddress::from(0x04));
+    assert_eq!(ProtectedFrame::verify(&misrouted), Err(ZanCanError::ChecksumMismatch));
+}
+
+#[test]
+fn test_rtr_frames_cannot_be_protected() {
+    let remote = ZanCanFrame::new_remote_request(ZanCanAddress::from(0x05), 2).expect("dlc fits");
+
+    assert_eq!(ProtectedFrame::new(&remote).err(), Some(ZanCanError::RemoteFrameNotSupported));
+}
@@ -0,0 +1,38 @@
+use zan_can::{address::ZanCanAddress, error::ZanCanError, message_data::DataIdentifier, segment::{segment_data, Reassembler}, zan_can_type::ZanCanFrameType};
+
+#[test]
+fn test_segmented_payload_reassembles_to_same() {
+    let data_id = DataIdentifier::try_from(0x0042u32).expect("error generating data identifier");
+    let mut payload = [0u8; 40];
+    for (i, b) in payload.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+
+    let mut reassembler: Reassembler<64> = Reassembler::new();
+    let mut result = None;
+    for frame in segment_data(ZanCanFrameType::SetData, ZanCanAddress::from(0x12), data_id, &payload).expect("expected payload to segment") {
+        assert_eq!(frame.frame_type(), ZanCanFrameType::SetData);
+        if let Some(done) = reassembler.push(&frame).expect("expected segment to be accepted") {
+            let mut copy = [0u8; 40];
+            copy.copy_from_slice(done);
+            result = Some(copy);
+        }
+    }
+
+    assert_eq!(result, Some(payload));
+}
+
+#[test]
+fn test_missing_segment_is_rejected() {
+    let data_id = DataIdentifier::try_from(0x0042u32).expect("error generating data identifier");
+    let payload = [0xAAu8; 12];
+    let mut segments = segment_data(ZanCanFrameType::SentData, ZanCanAddress::from(0x12), data_id, &payload).expect("expected payload to segment");
+
+    let mut reassembler: Reassembler<64> = Reassembler::new();
+    let first = segments.next().expect("expected first segment");
+    let _skipped = segments.next();
+    let third = segments.next().expect("expected third segment");
+
+    assert_eq!(reassembler.push(&first), Ok(None));
+    assert_eq!(reassembler.push(&third), Err(ZanCanError::SegmentOutOfOrder));
+}
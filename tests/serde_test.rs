@@ -0,0 +1,31 @@
+#![cfg(feature = "serde")]
+
+use zan_can::{address::ZanCanAddress, emergency::{EmergencyStatus, EmergencyReason}, message_data::{DataIdentifier, DataMessage}, ZanCanFrame};
+
+#[test]
+fn test_frame_serializes_only_significant_bytes() {
+    let frame = ZanCanFrame::new_emergency(ZanCanAddress::from(0x12), EmergencyStatus::Stop, EmergencyReason::OVER_TEMPERATURE);
+
+    let json = serde_json::to_string(&frame).expect("expected frame to serialize");
+    let back: ZanCanFrame = serde_json::from_str(&json).expect("expected frame to deserialize");
+
+    assert_eq!(json, r#"{"id":18,"frame_type":"Emergency","data":[2,1]}"#);
+    assert_eq!(back, frame);
+}
+
+#[test]
+fn test_data_message_round_trips() {
+    let data_id = DataIdentifier::try_from(0x04u32).expect("error generating data identifier");
+    let message = DataMessage::new(data_id, &[0x01, 0x02]).expect("error generating data message");
+
+    let json = serde_json::to_string(&message).expect("expected message to serialize");
+    let back: DataMessage = serde_json::from_str(&json).expect("expected message to deserialize");
+
+    assert_eq!(back.data_identifier(), data_id);
+    assert_eq!(back.data(), &[0x01, 0x02]);
+}
+
+#[test]
+fn test_invalid_reason_is_rejected() {
+    assert!(serde_json::from_str::<EmergencyReason>("32768").is_err());
+}
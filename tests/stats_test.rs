@@ -0,0 +1,40 @@
+use zan_can::{address::{ExtendedAddress, ZanCanAddress}, error::ErrorCode, stats::BusStats, zan_can_type::ZanCanFrameType, ZanCanFrame};
+
+#[test]
+fn test_stats_tally_by_type_and_address() {
+    let a = ZanCanAddress::from(0x01);
+    let b = ZanCanAddress::from(0xFF);
+    let mut stats = BusStats::new();
+
+    for frame in [ZanCanFrame::new_heartbeat(a, 0), ZanCanFrame::new_heartbeat(b, 0), ZanCanFrame::new_error(b, ErrorCode::BusOff)] {
+        stats.record(&frame);
+    }
+
+    assert_eq!(stats.total(), 3);
+    assert_eq!(stats.count(ZanCanFrameType::Heartbeat), 2);
+    assert_eq!(stats.count(ZanCanFrameType::Error), 1);
+    assert_eq!(stats.count(ZanCanFrameType::Emergency), 0);
+    assert_eq!(stats.count_from(a), 1);
+    assert_eq!(stats.count_from(b), 2);
+
+    stats.reset();
+    assert_eq!(stats.total(), 0);
+}
+
+#[test]
+fn test_stats_count_wide_extended_addresses_by_type() {
+    let addr = ZanCanAddress::from(0x01);
+    let heartbeat = ZanCanFrame::new_heartbeat(addr, 0);
+    let mut stats = BusStats::new();
+
+    stats.record(&heartbeat.with_extended_address(ExtendedAddress::from_const(0x0101)));
+    assert_eq!(stats.total(), 1);
+    assert_eq!(stats.count(ZanCanFrameType::Heartbeat), 1);
+    assert_eq!(stats.count_from_wide_addresses(), 1);
+    //Not counted under its low byte
+    assert_eq!(stats.count_from(addr), 0);
+
+    stats.record(&heartbeat.with_extended_address(ExtendedAddress::from(addr)));
+    assert_eq!(stats.count_from(addr), 1);
+    assert_eq!(stats.count_from_wide_addresses(), 1);
+}
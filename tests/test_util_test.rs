@@ -0,0 +1,59 @@
+#![cfg(feature = "test-util")]
+
+use zan_can::{address::ZanCanAddress, emergency::{EmergencyReason, EmergencyStatus}, error::{ErrorCode, ZanCanError}, message_data::{DataIdentifier, DataMessage, NakReason}, zan_can_type::ZanCanFrameType, ZanCanFrame};
+use embedded_can::{Id, StandardId};
+
+#[test]
+fn test_unchecked_frames_reach_the_decoder_error_paths() {
+    let id = Id::Standard(StandardId::new(0x0712).expect("error generating id"));
+
+    let short = ZanCanFrame::raw_unchecked(id, [0u8; 8], 3, ZanCanFrameType::Heartbeat);
+    assert_eq!(short.decode_heartbeat(), Err(ZanCanError::TruncatedData));
+
+    //Stored type disagrees with the id, decoders go by the stored type
+    let mismatched = ZanCanFrame::raw_unchecked(id, [0u8; 8], 2, ZanCanFrameType::Error);
+    assert_eq!(mismatched.decode_heartbeat(), Err(ZanCanError::WrongFrameType { expected: ZanCanFrameType::Heartbeat, found: ZanCanFrameType::Error }));
+    assert_eq!(mismatched.type_code(), 0x07);
+}
+
+#[test]
+fn test_validate_catches_broken_invariants() {
+    let id = Id::Standard(StandardId::new(0x0712).expect("error generating id"));
+
+    assert_eq!(ZanCanFrame::raw_unchecked(id, [0u8; 8], 4, ZanCanFrameType::Heartbeat).validate(), Ok(()));
+    assert_eq!(ZanCanFrame::raw_unchecked(id, [0u8; 8], 3, ZanCanFrameType::Heartbeat).validate(), Err(ZanCanError::TruncatedData));
+    assert_eq!(ZanCanFrame::raw_unchecked(id, [0u8; 8], 4, ZanCanFrameType::Error).validate(), Err(ZanCanError::TypeIdMismatch { id_type: ZanCanFrameType::Heartbeat, stored: ZanCanFrameType::Error }));
+}
+
+#[test]
+fn test_resolved_type_reports_mismatch() {
+    let id = Id::Standard(StandardId::new(0x0712).expect("error generating id"));
+
+    assert_eq!(ZanCanFrame::raw_unchecked(id, [0u8; 8], 4, ZanCanFrameType::Heartbeat).resolved_type(), Ok(ZanCanFrameType::Heartbeat));
+    assert_eq!(ZanCanFrame::raw_unchecked(id, [0u8; 8], 4, ZanCanFrameType::SetData).resolved_type(), Err(ZanCanError::TypeIdMismatch { id_type: ZanCanFrameType::Heartbeat, stored: ZanCanFrameType::SetData }));
+}
+
+#[test]
+fn test_parse_builds_each_frame_type() {
+    let addr = ZanCanAddress::from(0x12);
+    let data_id = DataIdentifier::try_from(0x0400u32).expect("error generating data identifier");
+
+    assert_eq!(ZanCanFrame::parse("EMERGENCY 0x12 Active OverTemp"), Ok(ZanCanFrame::new_emergency(addr, EmergencyStatus::Stop, EmergencyReason::OVER_TEMPERATURE)));
+    assert_eq!(ZanCanFrame::parse("error 18 BusOff cleared"), Ok(ZanCanFrame::new_error_cleared(addr, ErrorCode::BusOff)));
+    assert_eq!(ZanCanFrame::parse("Heartbeat 0x12 1000"), Ok(ZanCanFrame::new_heartbeat(addr, 1000)));
+    assert_eq!(ZanCanFrame::parse("SET_DATA 0x12 0x0400 1 2"), Ok(ZanCanFrame::new_set_data(addr, DataMessage::new(data_id, &[1, 2]).expect("fits"))));
+    assert_eq!(ZanCanFrame::parse("REQUEST_DATA 0x12 0x0400 7"), ZanCanFrame::new_request_data_with_args(addr, data_id, &[7]).map_err(|_| unreachable!()));
+    assert_eq!(ZanCanFrame::parse("DATA_NAK 0x12 0x0400 Busy"), Ok(ZanCanFrame::new_data_nak(addr, data_id, NakReason::Busy)));
+    assert_eq!(ZanCanFrame::parse("TIME 0x12 1 2 3 4 5 6 7 8").map(|f| f.raw_payload().len()), Ok(8));
+}
+
+#[test]
+fn test_parse_names_the_bad_token() {
+    let err = ZanCanFrame::parse("EMERGENCY 0x12 Stop Meltdown").expect_err("reason is unknown");
+    assert_eq!(err.token, "Meltdown");
+    assert_eq!(err.to_string(), "unknown emergency reason 'Meltdown'");
+
+    assert_eq!(ZanCanFrame::parse("ERROR 0x21 Over").expect_err("several codes start with Over").message, "ambiguous name");
+    assert_eq!(ZanCanFrame::parse("HEARTBEAT").expect_err("no address").to_string(), "missing address");
+    assert_eq!(ZanCanFrame::parse("HEARTBEAT 0x12 5 6").expect_err("extra token").token, "6");
+}
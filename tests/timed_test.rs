@@ -0,0 +1,17 @@
+use zan_can::{address::ZanCanAddress, timed::TimedFrame, zan_can_type::ZanCanFrameType, ZanCanFrame};
+
+#[test]
+fn test_timed_frame_reads_through_to_the_frame() {
+    let frame = ZanCanFrame::new_heartbeat(ZanCanAddress::from(0x08), 42);
+    let timed = TimedFrame::new(frame, 1_500u64);
+
+    assert_eq!(*timed.timestamp(), 1_500);
+    assert_eq!(timed.frame(), &frame);
+    assert_eq!(timed.frame_type(), ZanCanFrameType::Heartbeat);
+    assert_eq!(timed.decode_heartbeat(), Ok(42));
+
+    //Copy when the timestamp is, so the original is still usable after this
+    let copy = timed;
+    assert_eq!(copy.into_parts(), (frame, 1_500));
+    assert_eq!(timed.address(), ZanCanAddress::from(0x08));
+}
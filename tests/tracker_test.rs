@@ -0,0 +1,81 @@
+use zan_can::{address::{ExtendedAddress, ZanCanAddress}, emergency::{EmergencyReason, EmergencyStatus}, error::{ErrorCode, ZanCanError}, tracker::{EmergencyTracker, ErrorTracker}, ZanCanFrame};
+
+#[test]
+fn test_emergency_tracker_empties_once_cleared() {
+    let a = ZanCanAddress::from(0x01);
+    let b = ZanCanAddress::from(0x02);
+    let mut tracker: EmergencyTracker<4> = EmergencyTracker::new();
+
+    tracker.record(&ZanCanFrame::new_emergency(a, EmergencyStatus::Stop, EmergencyReason::OVER_VOLTAGE)).expect("should record");
+    tracker.record(&ZanCanFrame::new_emergency(a, EmergencyStatus::Stop, EmergencyReason::MANUAL_STOP)).expect("should record");
+    //repeats don't take up another slot
+    tracker.record(&ZanCanFrame::new_emergency(a, EmergencyStatus::Stop, EmergencyReason::MANUAL_STOP)).expect("should record");
+
+    assert!(tracker.any_active());
+    assert_eq!(tracker.active_reasons(a).count(), 2);
+    assert!(!tracker.is_active(b));
+
+    tracker.record(&ZanCanFrame::new_emergency(a, EmergencyStatus::Clear, EmergencyReason::OVER_VOLTAGE)).expect("should record");
+    assert!(tracker.active_reasons(a).eq([EmergencyReason::MANUAL_STOP]));
+
+    tracker.record(&ZanCanFrame::new_emergency(a, EmergencyStatus::Clear, EmergencyReason::MANUAL_STOP)).expect("should record");
+    assert_eq!(tracker.active_reasons(a).count(), 0);
+    assert!(!tracker.any_active());
+}
+
+#[test]
+fn test_emergency_tracker_rejects_other_frames_and_overflow() {
+    let mut tracker: EmergencyTracker<1> = EmergencyTracker::new();
+    let addr = ZanCanAddress::from(0x01);
+
+    assert!(tracker.record(&ZanCanFrame::new_error(addr, ErrorCode::BusOff)).is_err());
+    tracker.record(&ZanCanFrame::new_emergency(addr, EmergencyStatus::Stop, EmergencyReason::OVER_VOLTAGE)).expect("should record");
+    assert_eq!(tracker.record(&ZanCanFrame::new_emergency(addr, EmergencyStatus::Stop, EmergencyReason::MANUAL_STOP)), Err(ZanCanError::BufferTooSmall));
+}
+
+#[test]
+fn test_error_tracker_follows_raise_and_clear() {
+    let a = ZanCanAddress::from(0x01);
+    let b = ZanCanAddress::from(0x02);
+    let mut tracker: ErrorTracker<4> = ErrorTracker::new();
+
+    tracker.record(&ZanCanFrame::new_error(a, ErrorCode::OverCurrent)).expect("should record");
+    tracker.record(&ZanCanFrame::new_error_with_status(a, ErrorCode::BusOff, false)).expect("should record");
+    tracker.record(&ZanCanFrame::new_error(b, ErrorCode::BusOff)).expect("should record");
+
+    assert_eq!(tracker.active_errors(a).count(), 2);
+    assert!(tracker.is_active(b));
+
+    tracker.record(&ZanCanFrame::new_error_cleared(a, ErrorCode::OverCurrent)).expect("should record");
+    tracker.record(&ZanCanFrame::new_error_cleared(a, ErrorCode::BusOff)).expect("should record");
+    assert_eq!(tracker.active_errors(a).count(), 0);
+    assert!(tracker.any_active());
+
+    tracker.record(&ZanCanFrame::new_error_cleared(b, ErrorCode::BusOff)).expect("should record");
+    assert!(!tracker.any_active());
+    assert!(tracker.record(&ZanCanFrame::new_heartbeat(a, 0)).is_err());
+}
+
+#[test]
+fn test_emergency_tracker_keeps_extended_nodes_apart_from_their_low_byte() {
+    let mut tracker: EmergencyTracker<4> = EmergencyTracker::new();
+    let addr = ZanCanAddress::from(0x01);
+    let stop = ZanCanFrame::new_emergency(addr, EmergencyStatus::Stop, EmergencyReason::MANUAL_STOP);
+
+    assert_eq!(tracker.record(&stop.with_extended_address(ExtendedAddress::from_const(0x0101))), Err(ZanCanError::ExtendedIdNotSupported));
+    assert!(!tracker.is_active(addr));
+    tracker.record(&stop.with_extended_address(ExtendedAddress::from(addr))).expect("address fits");
+    assert!(tracker.is_active(addr));
+}
+
+#[test]
+fn test_error_tracker_keeps_extended_nodes_apart_from_their_low_byte() {
+    let mut tracker: ErrorTracker<4> = ErrorTracker::new();
+    let addr = ZanCanAddress::from(0x01);
+    let error = ZanCanFrame::new_error(addr, ErrorCode::BusOff);
+
+    assert_eq!(tracker.record(&error.with_extended_address(ExtendedAddress::from_const(0x0101))), Err(ZanCanError::ExtendedIdNotSupported));
+    assert!(!tracker.is_active(addr));
+    tracker.record(&error.with_extended_address(ExtendedAddress::from(addr))).expect("address fits");
+    assert!(tracker.is_active(addr));
+}
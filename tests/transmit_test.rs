@@ -0,0 +1,71 @@
+mod common;
+
+use zan_can::{address::ZanCanAddress, transmit::{transmit, TransmitError}, ZanCanFrame};
+use embedded_can::{blocking, ErrorKind, Frame};
+use common::DriverFrame;
+
+#[derive(Debug, PartialEq)]
+struct BusError;
+
+impl embedded_can::Error for BusError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+//Records what it was asked to send and fails once it has taken `capacity` frames
+struct LoopbackCan {
+    sent: Vec<ZanCanFrame>,
+    capacity: usize
+}
+
+impl blocking::Can for LoopbackCan {
+    type Frame = DriverFrame;
+    type Error = BusError;
+
+    fn transmit(&mut self, frame: &DriverFrame) -> Result<(), BusError> {
+        if self.sent.len() == self.capacity {
+            return Err(BusError);
+        }
+        self.sent.push(ZanCanFrame::from_frame_truncating(DriverFrame::new(frame.id(), frame.data()).expect("driver frame copies")).expect("frame id holds a known type"));
+        Ok(())
+    }
+
+    fn receive(&mut self) -> Result<DriverFrame, BusError> {
+        Err(BusError)
+    }
+}
+
+#[test]
+fn test_transmit_hands_the_converted_frame_to_the_driver() {
+    let frame = ZanCanFrame::new_heartbeat(ZanCanAddress::from(0x42), 7);
+    let mut can = LoopbackCan { sent: Vec::new(), capacity: 1 };
+
+    assert_eq!(transmit(&frame, &mut can), Ok(()));
+    assert_eq!(can.sent, vec![frame]);
+    assert_eq!(transmit(&frame, &mut can), Err(TransmitError::Can(BusError)));
+}
+
+#[cfg(feature = "nb")]
+#[test]
+fn test_transmit_nb_passes_would_block_through() {
+    use zan_can::transmit::transmit_nb;
+
+    struct FullCan;
+
+    impl embedded_can::nb::Can for FullCan {
+        type Frame = DriverFrame;
+        type Error = BusError;
+
+        fn transmit(&mut self, _frame: &DriverFrame) -> nb::Result<Option<DriverFrame>, BusError> {
+            Err(nb::Error::WouldBlock)
+        }
+
+        fn receive(&mut self) -> nb::Result<DriverFrame, BusError> {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+
+    let frame = ZanCanFrame::new_heartbeat(ZanCanAddress::from(0x42), 7);
+    assert!(matches!(transmit_nb(&frame, &mut FullCan), Err(nb::Error::WouldBlock)));
+}
@@ -0,0 +1,32 @@
+use zan_can::{address::ZanCanAddress, emergency::{EmergencyReason, EmergencyStatus}, error::ZanCanError, message_data::{DataIdentifier, DataMessage}, tx_queue::TxQueue, zan_can_type::ZanCanFrameType, ZanCanFrame};
+
+fn data_frame(addr: u8) -> ZanCanFrame {
+    let data_id = DataIdentifier::try_from(0x01u32).expect("error generating data identifier");
+    ZanCanFrame::new_sent_data(ZanCanAddress::from(addr), DataMessage::with_payload(data_id, [addr]))
+}
+
+#[test]
+fn test_emergency_pushed_last_comes_out_first() {
+    let mut queue: TxQueue<4> = TxQueue::new();
+    queue.push(data_frame(0x03)).expect("queue has room");
+    queue.push(data_frame(0x01)).expect("queue has room");
+    queue.push(ZanCanFrame::new_heartbeat(ZanCanAddress::from(0x01), 0)).expect("queue has room");
+    queue.push(ZanCanFrame::new_emergency(ZanCanAddress::from(0x09), EmergencyStatus::Stop, EmergencyReason::MANUAL_STOP)).expect("queue has room");
+
+    assert_eq!(queue.pop().map(|f| f.frame_type()), Some(ZanCanFrameType::Emergency));
+    assert_eq!(queue.pop(), Some(data_frame(0x01)));
+    assert_eq!(queue.pop(), Some(data_frame(0x03)));
+    assert_eq!(queue.pop().map(|f| f.frame_type()), Some(ZanCanFrameType::Heartbeat));
+    assert_eq!(queue.pop(), None);
+}
+
+#[test]
+fn test_full_queue_rejects_push() {
+    let mut queue: TxQueue<1> = TxQueue::new();
+    queue.push(data_frame(0x01)).expect("queue has room");
+
+    assert!(queue.is_full());
+    assert_eq!(queue.push(data_frame(0x02)), Err(ZanCanError::QueueFull));
+    assert_eq!(queue.peek(), Some(&data_frame(0x01)));
+    assert_eq!(queue.len(), 1);
+}
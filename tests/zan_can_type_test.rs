@@ -0,0 +1,96 @@
+use zan_can::{address::{ZanCanAddress, ADDRESS_BIT_LENGTH, MAX_ADDRESS}, emergency::{EmergencyStatus, EmergencyReason}, error::{ErrorCode, ZanCanError}, id_from_type_and_address, zan_can_type::ZanCanFrameType, ZanCanFrame};
+use embedded_can::{Frame, Id, StandardId};
+
+#[test]
+fn test_every_frame_type_round_trips_through_id() {
+    for t in ZanCanFrameType::all() {
+        let id = id_from_type_and_address(t, ZanCanAddress::from(0x33)).expect("expected id to fit");
+        let frame = ZanCanFrame::new(id, &[]).expect("expected frame to be created");
+
+        assert_eq!(frame.frame_type(), t);
+    }
+}
+
+#[test]
+fn test_every_frame_type_and_address_round_trips() {
+    for t in ZanCanFrameType::all() {
+        for a in 0..=u8::MAX {
+            let id = id_from_type_and_address(t, ZanCanAddress::from(a)).expect("expected id to fit");
+
+            assert_eq!(ZanCanFrameType::try_from(id), Ok(t));
+        }
+    }
+}
+
+#[test]
+fn test_type_code_one_is_data_nak() {
+    let id = Id::Standard(StandardId::new(0x0112).expect("error generating id"));
+
+    assert_eq!(ZanCanFrameType::try_from(id), Ok(ZanCanFrameType::DataNak));
+}
+
+#[test]
+fn test_frame_classification() {
+    let data: Vec<ZanCanFrameType> = ZanCanFrameType::all().filter(|t| t.is_data()).collect();
+    let faults: Vec<ZanCanFrameType> = ZanCanFrameType::all().filter(|t| t.is_fault()).collect();
+
+    assert_eq!(data, [ZanCanFrameType::DataNak, ZanCanFrameType::SentData, ZanCanFrameType::RequestData, ZanCanFrameType::SetData]);
+    assert_eq!(faults, [ZanCanFrameType::Emergency, ZanCanFrameType::Error]);
+    assert!(!ZanCanFrame::new_heartbeat(ZanCanAddress::from(0x01), 0).is_data_frame());
+}
+
+#[test]
+fn test_type_codes_fit_above_the_address_bits() {
+    let max_code = ZanCanFrameType::all().map(u8::from).max().expect("there is always a frame type");
+
+    assert!(((max_code as u16) << ADDRESS_BIT_LENGTH) | MAX_ADDRESS as u16 <= StandardId::MAX.as_raw());
+    for t in ZanCanFrameType::all() {
+        assert_eq!((u8::from(t) as u16) << ADDRESS_BIT_LENGTH & MAX_ADDRESS as u16, 0);
+    }
+}
+
+#[test]
+fn test_reserved_type_codes_are_rejected() {
+    for t in ZanCanFrameType::all() {
+        assert_eq!(ZanCanFrameType::try_from_code(u8::from(t)), Ok(t));
+    }
+
+    assert_eq!(ZanCanFrameType::try_from_code(0x08), Err(ZanCanError::UnknownFrameType(0x08)));
+    assert_eq!(ZanCanFrameType::try_from_code(0xFF), Err(ZanCanError::UnknownFrameType(0xFF)));
+}
+
+#[test]
+fn test_expected_len_matches_constructors() {
+    let addr = ZanCanAddress::from(0x33);
+    let fixed = [
+        ZanCanFrame::new_emergency(addr, EmergencyStatus::Stop, EmergencyReason::MANUAL_STOP),
+        ZanCanFrame::new_error(addr, ErrorCode::BusOff),
+        ZanCanFrame::new_heartbeat(addr, 1)
+    ];
+
+    for frame in fixed {
+        assert_eq!(frame.frame_type().expected_len(), Some(frame.dlc()));
+    }
+    assert_eq!(ZanCanFrameType::Time.expected_len(), Some(8));
+    for t in ZanCanFrameType::all().filter(|t| t.is_data()) {
+        assert_eq!(t.expected_len(), None);
+    }
+}
+
+#[test]
+fn test_frame_types_sort_in_arbitration_order() {
+    let mut types = [ZanCanFrameType::Heartbeat, ZanCanFrameType::SentData, ZanCanFrameType::Emergency, ZanCanFrameType::Error];
+    types.sort();
+
+    assert_eq!(types, [ZanCanFrameType::Emergency, ZanCanFrameType::Error, ZanCanFrameType::SentData, ZanCanFrameType::Heartbeat]);
+    assert!(ZanCanFrameType::Emergency.priority() < ZanCanFrameType::Heartbeat.priority());
+    assert_eq!(ZanCanFrame::new_heartbeat(ZanCanAddress::from(0x01), 0).priority(), ZanCanFrameType::Heartbeat.priority());
+}
+
+#[test]
+fn test_name_matches_the_variant() {
+    for f_type in ZanCanFrameType::all() {
+        assert_eq!(f_type.name(), format!("{:?}", f_type));
+    }
+    assert_eq!(ZanCanFrameType::SetData.name(), "SetData");
+}